@@ -0,0 +1,94 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use currency_rs::{Currency, CurrencyOpts};
+use rand::thread_rng;
+use rand::Rng;
+
+fn generate_random_currency_vec(
+    size: u32,
+    opts: CurrencyOpts,
+) -> Vec<Currency> {
+    (1..=size)
+        .map(|_| Currency::new_float(thread_rng().gen_range(-1e6..1e6), Some(opts.clone())))
+        .collect()
+}
+
+//----------------------------------------------------------//
+
+fn format(values: &[Currency]) -> usize {
+    values.iter().map(|value| value.format().len()).sum()
+}
+
+fn format_benchmark(c: &mut Criterion) {
+    let values = generate_random_currency_vec(1_000, CurrencyOpts::new());
+
+    c.bench_function("format benchmark", |b| {
+        b.iter(|| format(black_box(&values)))
+    });
+}
+
+//----------------------------------------------------------//
+
+fn format_euro_benchmark(c: &mut Criterion) {
+    let opts = CurrencyOpts::new()
+        .set_symbol("€")
+        .set_separator(".")
+        .set_decimal(",")
+        .set_pattern("# !")
+        .set_negative_pattern("-# !");
+
+    let values = generate_random_currency_vec(1_000, opts);
+
+    c.bench_function("format euro benchmark", |b| {
+        b.iter(|| format(black_box(&values)))
+    });
+}
+
+//----------------------------------------------------------//
+
+fn write_formatted(
+    values: &[Currency],
+    out: &mut String,
+) -> usize {
+    out.clear();
+
+    for value in values {
+        value.write_formatted(out).unwrap();
+        out.push('\n');
+    }
+
+    out.len()
+}
+
+fn write_formatted_benchmark(c: &mut Criterion) {
+    let values = generate_random_currency_vec(1_000, CurrencyOpts::new());
+    let mut out = String::new();
+
+    c.bench_function("write formatted benchmark", |b| {
+        b.iter(|| write_formatted(black_box(&values), &mut out))
+    });
+}
+
+//----------------------------------------------------------//
+
+fn share_opts(opts: &CurrencyOpts) -> Vec<Currency> {
+    (0..1_000)
+        .map(|i| Currency::new_float(i as f64, Some(opts.clone())))
+        .collect()
+}
+
+fn share_opts_benchmark(c: &mut Criterion) {
+    let opts = CurrencyOpts::for_code("EUR").unwrap();
+
+    c.bench_function("share opts benchmark", |b| {
+        b.iter(|| share_opts(black_box(&opts)))
+    });
+}
+
+criterion_group!(
+    benches,
+    format_benchmark,
+    format_euro_benchmark,
+    write_formatted_benchmark,
+    share_opts_benchmark
+);
+criterion_main!(benches);
@@ -0,0 +1,62 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use currency_rs::{Currency, CurrencyOpts};
+use rand::thread_rng;
+use rand::Rng;
+
+fn generate_random_string_vec(
+    size: u32,
+    format: fn(f64) -> String,
+) -> Vec<String> {
+    (1..=size)
+        .map(|_| format(thread_rng().gen_range(-1e6..1e6)))
+        .collect()
+}
+
+//----------------------------------------------------------//
+
+fn parse(
+    values: &[String],
+    opts: &CurrencyOpts,
+) -> i128 {
+    values
+        .iter()
+        .map(|value| {
+            Currency::new_string(value, Some(opts.clone()))
+                .unwrap()
+                .minor_units()
+        })
+        .sum()
+}
+
+fn parse_benchmark(c: &mut Criterion) {
+    let opts = CurrencyOpts::new();
+    let values = generate_random_string_vec(1_000, |v| Currency::new_float(v, None).format());
+
+    c.bench_function("parse string benchmark", |b| {
+        b.iter(|| parse(black_box(&values), &opts))
+    });
+}
+
+//----------------------------------------------------------//
+
+fn parse_euro_benchmark(c: &mut Criterion) {
+    let opts = CurrencyOpts::new()
+        .set_symbol("€")
+        .set_separator(".")
+        .set_decimal(",");
+    let values = generate_random_string_vec(1_000, |v| {
+        let opts = CurrencyOpts::new()
+            .set_symbol("€")
+            .set_separator(".")
+            .set_decimal(",");
+
+        format!("({})", Currency::new_float(v.abs(), Some(opts)).format())
+    });
+
+    c.bench_function("parse euro string benchmark", |b| {
+        b.iter(|| parse(black_box(&values), &opts))
+    });
+}
+
+criterion_group!(benches, parse_benchmark, parse_euro_benchmark);
+criterion_main!(benches);
@@ -1,35 +1,35 @@
+use crate::currency_parser::StrictParser;
 use crate::{
-    allow_negative_values, convert_any_decimal_values, format_dollar,
-    replace_any_non_numeric_values, CurrencyErr, CurrencyOpts,
+    currency_math, Backend, CurrencyErr, CurrencyOpts, ExchangeRate, IsoCurrency, Locale,
+    ParseErrKind, ParseError, RateProvider, RoundingMode,
 };
 
+/// An amount of money, stored as minor units in the backend `B` (exact `i128` by default).
 #[derive(Debug, Clone)]
-pub struct Currency {
-    pub(crate) value: f64,
-    pub(crate) int_value: f64,
+pub struct Currency<B: Backend = i128> {
+    pub(crate) int_value: B,
     pub(crate) opts: CurrencyOpts,
 }
 
 impl Currency {
-    /// It returns the value of the currency.
+    /// It creates a new currency object from a f64.
     ///
-    /// Returns:
+    /// Arguments:
     ///
-    /// f64 value.
-    pub fn value(&self) -> f64 {
-        self.value
-    }
-
-    /// It returns the int_value of the currency.
+    /// * `value`: The value of the currency.
+    /// * `opts`: CurrencyOpts
     ///
     /// Returns:
     ///
-    /// f64 int_value.
-    pub fn int_value(&self) -> f64 {
-        self.int_value
+    /// A new instance of the Currency struct.
+    pub fn new_float(
+        value: f64,
+        opts: Option<CurrencyOpts>,
+    ) -> Self {
+        Self::from_float(value, opts)
     }
 
-    /// It creates a new currency object from a f64.
+    /// It creates a new currency object from a f64, failing instead of saturating.
     ///
     /// Arguments:
     ///
@@ -38,19 +38,12 @@ impl Currency {
     ///
     /// Returns:
     ///
-    /// A new instance of the Currency struct.
-    pub fn new_float(
+    /// A new instance of the Currency struct, see `Currency::try_from_float` for the errors.
+    pub fn try_new_float(
         value: f64,
         opts: Option<CurrencyOpts>,
-    ) -> Self {
-        let currency_options = match opts {
-            Some(options) => options,
-            None => CurrencyOpts::new(),
-        };
-
-        let v = Self::parse(value, &currency_options, true);
-
-        Self::new(v, currency_options)
+    ) -> Result<Self, CurrencyErr> {
+        Self::try_from_float(value, opts)
     }
 
     /// It creates a new currency object from a string.
@@ -67,14 +60,7 @@ impl Currency {
         value: &str,
         opts: Option<CurrencyOpts>,
     ) -> Result<Self, CurrencyErr> {
-        let currency_options = match opts {
-            Some(options) => options,
-            None => CurrencyOpts::default(),
-        };
-
-        let v = Self::parse_string(value, &currency_options, true)?;
-
-        Ok(Self::new(v, currency_options))
+        Self::from_string(value, opts)
     }
 
     /// It creates a new currency object from a currency object.
@@ -91,455 +77,1378 @@ impl Currency {
         cur: Self,
         opts: Option<CurrencyOpts>,
     ) -> Self {
-        let currency_options = match opts {
-            Some(options) => options,
-            None => CurrencyOpts::default(),
-        };
-
-        let v = Self::parse(cur.value, &currency_options, true);
-
-        Self::new(v, currency_options)
+        Self::from_cur(cur, opts)
     }
+}
 
-    /// It creates a new instance of the Currency struct.
-    ///
-    /// Arguments:
-    ///
-    /// * `value`: The value of the currency.
-    /// * `opts`: CurrencyOpts
+impl<B: Backend> Currency<B> {
+    /// It returns the value of the currency.
     ///
     /// Returns:
     ///
-    /// A new instance of the Currency struct.
-    fn new(
-        v: f64,
-        opts: CurrencyOpts,
-    ) -> Self {
-        let precision = Self::pow(opts.precision());
-
-        let int_value = v;
-
-        let value = int_value / precision;
+    /// f64 value.
+    pub fn value(&self) -> f64 {
+        let precision = self.precision();
 
-        Self {
-            value,
-            int_value,
-            opts,
-        }
+        self.int_value.to_f64(precision) / Self::pow(precision)
     }
 
-    /// > This function takes a value, a set of options, and a boolean value, and returns a float
+    /// It returns the int_value of the currency.
     ///
-    /// Arguments:
+    /// Returns:
     ///
-    /// * `value`: The value to be parsed.
-    /// * `opts`: CurrencyOpts
-    /// * `use_rounding`: If true, the value will be rounded to the nearest integer.
+    /// f64 int_value.
+    pub fn int_value(&self) -> f64 {
+        self.int_value.to_f64(self.precision())
+    }
+
+    /// It returns the exact number of minor units (e.g. cents) held by the currency.
     ///
     /// Returns:
     ///
-    /// a f64 value.
-    fn parse(
-        value: f64,
-        opts: &CurrencyOpts,
-        use_rounding: bool,
-    ) -> f64 {
-        let mut v: f64 = value;
-
-        let from_cents = opts.from_cents();
+    /// i128 minor units.
+    pub fn minor_units(&self) -> i128 {
+        self.int_value.to_minor(self.precision())
+    }
 
-        let precision = Self::pow(opts.precision());
+    /// It checks whether the currency holds no minor units.
+    pub fn is_zero(&self) -> bool {
+        self.minor_units() == 0
+    }
 
-        if !from_cents {
-            v *= precision; // scale number to integer value
-            v = Self::round_dp(v, 4)
-        }
+    /// It checks whether the currency is greater than zero.
+    pub fn is_positive(&self) -> bool {
+        self.minor_units() > 0
+    }
 
-        if use_rounding {
-            Self::round(v)
-        } else {
-            v
-        }
+    /// It checks whether the currency is less than zero.
+    pub fn is_negative(&self) -> bool {
+        self.minor_units() < 0
     }
 
-    /// It takes a string, removes all non-numeric characters, converts it to a float, and then rounds it
-    /// to the nearest integer
+    /// It returns the sign of the currency.
     ///
-    /// Arguments:
+    /// Returns:
     ///
-    /// * `value`: The string value to be parsed.
-    /// * `opts`: CurrencyOpts
-    /// * `use_rounding`: If true, the value will be rounded to the nearest integer.
-    fn parse_string(
-        value: &str,
-        opts: &CurrencyOpts,
-        use_rounding: bool,
-    ) -> Result<f64, CurrencyErr> {
-        let decimal = opts.decimal();
-
-        let from_cents = opts.from_cents();
-
-        let precision = Self::pow(opts.precision());
-
-        let value_allow_negative = allow_negative_values(value);
-
-        let numeric_values = replace_any_non_numeric_values(decimal.clone(), value_allow_negative);
-
-        let value = convert_any_decimal_values(decimal, numeric_values);
-
-        match value.parse::<f64>() {
-            Ok(mut parsed_val) => {
-                if !from_cents {
-                    parsed_val *= precision; // scale number to integer value
-                    parsed_val = Self::round_dp(parsed_val, 4);
-                }
-
-                if use_rounding {
-                    Ok(Self::round(parsed_val))
-                } else {
-                    Ok(parsed_val)
-                }
-            }
-            Err(err) => {
-                if opts.error_on_invalid() {
-                    return Err(CurrencyErr::ParseErr(err.to_string()));
-                }
-
-                Ok(0.)
-            }
-        }
+    /// `-1`, `0` or `1`.
+    pub fn signum(&self) -> i32 {
+        self.minor_units().signum() as i32
     }
 
-    pub fn format(&self) -> String {
-        let precision = self.opts.precision();
-
-        let increment = self.opts.increment();
-
-        let rounded_value = Self::rounding(self.value, increment);
-
-        let pos_pattern = self.opts.pattern();
-
-        let negative_pattern = self.opts.negative_pattern();
-
-        let symbol = self.opts.symbol();
-
-        let separator = self.opts.separator();
-
-        let decimal = self.opts.decimal();
-
-        let mut currency: String =
-            Self::round_dp_to_string(rounded_value, self.opts.precision() as usize);
-
-        currency = currency.replacen('-', "", 1);
-
-        let split_currency: Vec<&str> = currency.split('.').collect();
-
-        let dollars = format_dollar(
-            self.opts.use_vedic(),
-            split_currency.first().unwrap().to_string(),
-            separator,
-        );
-
-        let mut cents = if split_currency.len() > 1 {
-            split_currency.last().unwrap().to_string()
-        } else {
-            "0".to_string()
-        };
-
-        cents = if precision > 0. {
-            decimal + &cents
-        } else {
-            "".to_string()
-        };
-
-        let pattern = if self.value >= 0. {
-            pos_pattern
-        } else {
-            negative_pattern
-        };
+    /// It returns the absolute value of the currency, keeping its options.
+    ///
+    /// > Currency::new_float(-2.51, None).abs().value(); // 2.51
+    ///
+    /// Returns:
+    ///
+    /// A new Currency, saturating when the backend cannot hold the absolute value.
+    pub fn abs(self) -> Self {
+        let precision = self.precision();
 
-        pattern
-            .replace('!', &symbol)
-            .replace('#', &(dollars + &cents))
+        Self {
+            int_value: B::from_minor(self.minor_units().saturating_abs(), precision),
+            opts: self.opts,
+        }
     }
 
-    /// It returns the cents of the value.
+    /// It returns the currency code of the options, if any.
     ///
     /// Returns:
     ///
-    /// The cents of the value.
-    pub fn cents(&self) -> u64 {
-        (self.int_value % Self::pow(self.opts.precision())) as u64
+    /// The upper case code, e.g. `"USD"`.
+    pub fn code(&self) -> Option<&str> {
+        self.opts.code()
     }
 
-    /// It returns the value of the dollar.
+    /// It returns the amount as stored by the backend.
     ///
     /// Returns:
     ///
-    /// The value of the money object.
-    pub fn dollars(&self) -> i64 {
-        (if self.value > 0. {
-            self.value.floor()
-        } else {
-            self.value.ceil()
-        }) as i64
+    /// The backend amount.
+    pub fn amount(&self) -> B {
+        self.int_value
     }
 
-    /// > It takes a number, converts it to an integer, adds it to the current integer value, and then
-    /// divides it by the precision
+    /// It creates a new currency object from a f64, stored in the backend `B`.
     ///
     /// Arguments:
     ///
-    /// * `number`: The number to add to the current value.
+    /// * `value`: The value of the currency.
+    /// * `opts`: CurrencyOpts
     ///
     /// Returns:
     ///
-    /// A new instance of the Money struct.
-    #[allow(clippy::should_implement_trait)]
-    pub fn add(
-        self,
-        number: f64,
+    /// A new instance of the Currency struct.
+    pub fn from_float(
+        value: f64,
+        opts: Option<CurrencyOpts>,
     ) -> Self {
-        let mut int_value = self.int_value;
-
-        let p = if self.opts.from_cents() {
-            1.
-        } else {
-            Self::pow(self.opts.precision())
-        };
+        let currency_options = opts.unwrap_or_default();
 
-        int_value += Self::parse(number, &self.opts, true);
+        let v = Self::parse(value, &currency_options);
 
-        Self::new_float(int_value / p, Some(self.opts))
+        Self::new(v, currency_options)
     }
 
-    /// > It takes a number, converts it to an integer, adds it to the current integer value, and then
-    /// divides it by the precision
+    /// It creates a new currency object from a f64, stored in the backend `B`, failing instead
+    /// of saturating.
     ///
     /// Arguments:
     ///
-    /// * `number`: The number to add to the current value.
+    /// * `value`: The value of the currency.
+    /// * `opts`: CurrencyOpts
     ///
     /// Returns:
     ///
-    /// A new instance of the Money struct.
-    pub fn add_string(
-        self,
-        number: &str,
+    /// A new instance of the Currency struct, or `CurrencyErr::InvalidOptions`,
+    /// `CurrencyErr::NonFiniteNumber`, `CurrencyErr::Overflow` or `CurrencyErr::PrecisionLoss`.
+    pub fn try_from_float(
+        value: f64,
+        opts: Option<CurrencyOpts>,
     ) -> Result<Self, CurrencyErr> {
-        let mut int_value = self.int_value;
+        let currency_options = opts.unwrap_or_default();
 
-        let p = if self.opts.from_cents() {
-            1.
-        } else {
-            Self::pow(self.opts.precision())
-        };
+        currency_options.validate()?;
+
+        let v = Self::checked_parse(value, &currency_options)?;
 
-        int_value += Self::parse_string(number, &self.opts, true)?;
+        let int_value = B::try_from_minor(v, currency_options.precision())?;
 
-        Ok(Self::new_float(int_value / p, Some(self.opts)))
+        Ok(Self::new(int_value, currency_options))
     }
 
-    /// > Subtracts a number from the current instance
+    /// It creates a new currency object from a string, stored in the backend `B`.
     ///
     /// Arguments:
     ///
-    /// * `number`: The number to subtract from the current instance.
+    /// * `value`: The value of the currency.
+    /// * `opts`: CurrencyOpts
     ///
     /// Returns:
     ///
-    /// A new instance of the Money struct.
-    pub fn subtract(
-        self,
-        number: f64,
-    ) -> Self {
-        let mut int_value = self.int_value;
+    /// A new instance of the Currency struct.
+    pub fn from_string(
+        value: &str,
+        opts: Option<CurrencyOpts>,
+    ) -> Result<Self, CurrencyErr> {
+        let currency_options = opts.unwrap_or_default();
 
-        let p = if self.opts.from_cents() {
-            1.
-        } else {
-            Self::pow(self.opts.precision())
-        };
+        currency_options.validate()?;
 
-        int_value -= Self::parse(number, &self.opts, true);
+        let v = Self::parse_string(value, &currency_options)?;
 
-        Self::new_float(int_value / p, Some(self.opts))
+        Ok(Self::new(v, currency_options))
     }
 
-    /// > Multiply the value of the current instance by the given number
+    /// It creates a new currency object from a currency object of any backend, stored in the
+    /// backend `B`.
     ///
     /// Arguments:
     ///
-    /// * `number`: The number to multiply the Money object by.
+    /// * `cur`: The currency object to be parsed.
+    /// * `opts`: Option<CurrencyOpts>
     ///
     /// Returns:
     ///
-    /// A new instance of the Money struct.
-    pub fn multiply(
-        self,
-        number: f64,
+    /// A new instance of the Currency struct.
+    pub fn from_cur<C: Backend>(
+        cur: Currency<C>,
+        opts: Option<CurrencyOpts>,
     ) -> Self {
-        let mut int_value = self.int_value;
-        let precision = if self.opts.from_cents() {
-            1.
-        } else {
-            Self::pow(self.opts.precision())
-        };
+        let currency_options = opts.unwrap_or_default();
 
-        int_value *= number;
+        let v = Self::parse_cur(&cur, &currency_options);
 
-        Self::new_float(int_value / precision, Some(self.opts))
+        Self::new(v, currency_options)
     }
 
-    /// It divides the number by the number passed in.
+    /// It creates a new instance of the Currency struct.
     ///
     /// Arguments:
     ///
-    /// * `number`: The number to divide by.
+    /// * `int_value`: The number of minor units of the currency.
+    /// * `opts`: CurrencyOpts
     ///
     /// Returns:
     ///
-    /// A new instance of the Money struct.
-    pub fn divide(
-        self,
-        number: f64,
+    /// A new instance of the Currency struct.
+    pub(crate) fn new(
+        int_value: B,
+        opts: CurrencyOpts,
     ) -> Self {
-        let mut int_value = self.int_value;
-        if number > 0. {
-            int_value /= Self::parse(number, &self.opts, false);
-            Self::new_float(int_value, Some(self.opts))
-        } else {
-            self
-        }
+        Self { int_value, opts }
     }
 
-    /// > This function takes a Money object and a count, and returns a vector of Money objects that are
-    /// split evenly
-    ///
-    /// Arguments:
-    ///
-    /// * `count`: The number of items to distribute the money into.
-    ///
-    /// Returns:
-    ///
-    /// A vector of Money objects.
-    pub fn distribute(
-        self,
-        mut count: i64,
-    ) -> Vec<Self> {
-        let int_value = self.int_value;
-
-        let mut distribution: Vec<Self> = vec![];
+    /// It returns the number of decimal places the minor units are kept at.
+    pub(crate) fn precision(&self) -> u32 {
+        self.opts.precision()
+    }
 
-        let split = if int_value >= 0. {
-            (int_value / count as f64).floor()
-        } else {
-            (int_value / count as f64).ceil()
-        };
+    /// It creates a currency of a number of minor units with the options of the current instance.
+    pub(crate) fn with_minor(
+        &self,
+        minor: i128,
+    ) -> Result<Self, CurrencyErr> {
+        let int_value = B::try_from_minor(minor, self.precision())?;
 
-        let mut pennies = int_value.abs() - split.abs() * (count as f64).abs();
+        Ok(Self::new(int_value, self.opts.clone()))
+    }
 
-        let precision = if self.opts.from_cents() {
-            1.
+    /// It returns the number of decimal places a value is parsed at, which is zero when the
+    /// value is already given in minor units.
+    fn parse_scale(opts: &CurrencyOpts) -> u32 {
+        if opts.from_cents() {
+            0
         } else {
-            Self::pow(self.opts.precision())
-        };
-
-        while count != 0 {
-            let mut item = Self::new_float(split / precision, Some(self.opts.clone()));
-
-            if pennies > 0. {
-                if int_value >= 0. {
-                    item = item.add(1. / precision);
-                } else {
-                    item = item.subtract(1. / precision);
-                }
-            }
-
-            distribution.push(item);
-
-            pennies -= 1.;
-            count -= 1;
+            opts.precision()
         }
+    }
 
-        distribution
+    /// It moves a value parsed at `parse_scale` to the precision of the options, which only
+    /// differs when the value was given in minor units.
+    fn from_parsed(
+        v: B,
+        opts: &CurrencyOpts,
+    ) -> B {
+        if opts.from_cents() {
+            B::from_minor(v.to_minor(0), opts.precision())
+        } else {
+            v
+        }
     }
 
-    /// It rounds a floating point number to the nearest integer
+    /// > This function takes a value and a set of options, and returns the number of minor units
+    /// > it represents
     ///
     /// Arguments:
     ///
-    /// * `r`: The number to round.
+    /// * `value`: The value to be parsed.
+    /// * `opts`: CurrencyOpts
     ///
     /// Returns:
     ///
-    /// The rounded value of the input.
-    fn round(r: f64) -> f64 {
-        r.round()
+    /// The minor units, rounded to the nearest integer.
+    fn parse(
+        value: f64,
+        opts: &CurrencyOpts,
+    ) -> B {
+        Self::from_parsed(
+            B::parse(value, Self::parse_scale(opts), opts.rounding_mode()),
+            opts,
+        )
     }
 
-    /// It rounds a number to the nearest increment.
+    /// It takes the exact value of a currency object and returns the number of minor units it
+    /// represents under another set of options.
     ///
     /// Arguments:
     ///
-    /// * `value`: The value to round.
-    /// * `increment`: The value to round to.
+    /// * `cur`: The currency object to be parsed.
+    /// * `opts`: CurrencyOpts
     ///
     /// Returns:
     ///
-    /// The value of the rounded number.
-    pub(crate) fn rounding(
-        value: f64,
-        increment: f64,
-    ) -> f64 {
-        Self::round(value / increment) * increment
+    /// The minor units, rounded to the nearest integer.
+    fn parse_cur<C: Backend>(
+        cur: &Currency<C>,
+        opts: &CurrencyOpts,
+    ) -> B {
+        let from = cur.precision();
+
+        let v = B::from_minor(cur.int_value.to_minor(from), from);
+
+        Self::from_parsed(
+            v.round_dp(from, Self::parse_scale(opts), opts.rounding_mode()),
+            opts,
+        )
     }
 
-    /// `pow` takes a `f64` and returns a `f64`
+    /// It converts a float into minor units, failing instead of saturating.
     ///
     /// Arguments:
     ///
-    /// * `p`: The power to raise 10 to.
+    /// * `value`: The value to be parsed.
+    /// * `opts`: CurrencyOpts
     ///
     /// Returns:
     ///
-    /// The value of 10 to the power of p.
-    fn pow(p: f64) -> f64 {
-        10_f64.powf(p)
+    /// The minor units, or why they could not be computed.
+    fn checked_parse(
+        value: f64,
+        opts: &CurrencyOpts,
+    ) -> Result<i128, CurrencyErr> {
+        currency_math::checked_scale_f64(value, Self::parse_scale(opts), opts.rounding_mode())
     }
 
-    /// It rounds a float to a given number of decimal places.
+    /// It moves the exact value of a currency object to the precision of the current instance,
+    /// whatever the `from_cents` option, since the value is already in minor units.
     ///
     /// Arguments:
     ///
-    /// * `v`: The value to round
-    /// * `dp`: The number of decimal places to round to.
+    /// * `cur`: The currency object to be rescaled.
     ///
     /// Returns:
     ///
-    /// A function that takes two arguments, a float and an unsigned integer, and returns a float.
-    fn round_dp(
-        v: f64,
-        dp: usize,
-    ) -> f64 {
-        format!("{v:.dp$}").parse::<f64>().unwrap()
+    /// The minor units, or `CurrencyErr::Overflow` when they do not fit.
+    pub(crate) fn minor_units_of<C: Backend>(
+        &self,
+        cur: &Currency<C>,
+    ) -> Result<i128, CurrencyErr> {
+        currency_math::rescale(
+            cur.minor_units(),
+            cur.precision(),
+            self.precision(),
+            self.opts.rounding_mode(),
+        )
+        .ok_or(CurrencyErr::Overflow)
     }
 
-    /// It takes a floating point number and a number of decimal places, and returns a string
-    /// representation of the floating point number rounded to the specified number of decimal places
+    /// It takes a string, removes all non-numeric characters, and converts the remaining decimal
+    /// into minor units, rounded to the nearest integer
     ///
     /// Arguments:
     ///
-    /// * `v`: the value to round
-    /// * `dp`: The number of decimal places to round to.
-    ///
-    /// Returns:
-    ///
-    /// A string
-    pub(crate) fn round_dp_to_string(
-        v: f64,
-        dp: usize,
-    ) -> String {
-        format!("{v:.dp$}")
+    /// * `value`: The string value to be parsed.
+    /// * `opts`: CurrencyOpts
+    fn parse_string(
+        value: &str,
+        opts: &CurrencyOpts,
+    ) -> Result<B, CurrencyErr> {
+        if opts.strict() {
+            return Self::parse_strict(value, opts);
+        }
+
+        #[cfg(not(feature = "fancy-regex"))]
+        let value = crate::currency_parser::lenient_number(value, opts.decimal());
+
+        #[cfg(feature = "fancy-regex")]
+        let value = crate::currency_regex::lenient_number(value, opts.decimal());
+
+        match B::parse_string(&value, Self::parse_scale(opts), opts.rounding_mode()) {
+            Some(parsed_val) => Ok(Self::from_parsed(parsed_val, opts)),
+            None => {
+                if opts.error_on_invalid() {
+                    let kind = if value.is_empty() || value == "-" {
+                        ParseErrKind::Empty
+                    } else if value.matches('.').count() > 1 {
+                        ParseErrKind::MultipleDecimals
+                    } else {
+                        ParseErrKind::UnexpectedChar
+                    };
+
+                    return Err(CurrencyErr::ParseErr(ParseError::new(
+                        kind,
+                        None,
+                        format!("invalid currency value: {value:?}"),
+                    )));
+                }
+
+                Ok(B::default())
+            }
+        }
+    }
+
+    /// It parses a string with the strict grammar, see `CurrencyOpts::set_strict`.
+    ///
+    /// Arguments:
+    ///
+    /// * `value`: The string value to be parsed.
+    /// * `opts`: CurrencyOpts
+    ///
+    /// Returns:
+    ///
+    /// The minor units, or a `CurrencyErr::ParseErr` with the byte offset and the kind of the
+    /// violation.
+    fn parse_strict(
+        value: &str,
+        opts: &CurrencyOpts,
+    ) -> Result<B, CurrencyErr> {
+        let parsed = StrictParser::new(value, opts)
+            .parse()
+            .map_err(CurrencyErr::ParseErr)?;
+
+        let overflow = || {
+            CurrencyErr::ParseErr(ParseError::new(
+                ParseErrKind::Overflow,
+                Some(parsed.offset),
+                format!("amount out of range at byte {}", parsed.offset),
+            ))
+        };
+
+        let v = currency_math::parse_decimal(
+            &parsed.value,
+            Self::parse_scale(opts),
+            opts.rounding_mode(),
+        )
+        .map_err(|_| overflow())?;
+
+        match B::try_from_minor(v, opts.precision()) {
+            Err(CurrencyErr::Overflow) => Err(overflow()),
+            result => result,
+        }
+    }
+
+    pub fn format(&self) -> String {
+        let mut formatted = String::new();
+
+        self.write_formatted(&mut formatted)
+            .expect("writing to a String does not fail");
+
+        formatted
+    }
+
+    /// > Writes the current instance formatted with its options, without allocating
+    ///
+    /// The output is the same as `format`.
+    ///
+    /// Arguments:
+    ///
+    /// * `w`: The writer, e.g. a `String` or a `fmt::Formatter`.
+    ///
+    /// Returns:
+    ///
+    /// The error of the writer, if any.
+    pub fn write_formatted<W: std::fmt::Write + ?Sized>(
+        &self,
+        w: &mut W,
+    ) -> std::fmt::Result {
+        let (rounded, precision) = self.rounded_minor(None);
+
+        self.write_pattern(w, rounded, precision)
+    }
+
+    /// It rounds the value with the increment and rounding mode of the options.
+    ///
+    /// Arguments:
+    ///
+    /// * `precision`: The number of decimal places wanted instead of the precision of the
+    ///   options, if any.
+    ///
+    /// Returns:
+    ///
+    /// The rounded minor units and their number of decimal places, which stays the precision of
+    /// the options when the value does not fit at the one asked.
+    pub(crate) fn rounded_minor(
+        &self,
+        precision: Option<u32>,
+    ) -> (i128, u32) {
+        let scale = self.precision();
+
+        let rounded = self
+            .int_value
+            .rounding(
+                self.opts.exact_increment(),
+                scale,
+                self.opts.rounding_mode(),
+            )
+            .to_minor(scale);
+
+        precision
+            .filter(|p| *p != scale)
+            .and_then(|p| {
+                currency_math::rescale(rounded, scale, p, self.opts.rounding_mode()).map(|v| (v, p))
+            })
+            .unwrap_or((rounded, scale))
+    }
+
+    /// It writes rounded minor units with the pattern, symbol and separators of the options,
+    /// the pattern being chosen by the sign of the unrounded value.
+    pub(crate) fn write_pattern<W: std::fmt::Write + ?Sized>(
+        &self,
+        w: &mut W,
+        rounded: i128,
+        precision: u32,
+    ) -> std::fmt::Result {
+        let pattern = if self.minor_units() >= 0 {
+            self.opts.pattern()
+        } else {
+            self.opts.negative_pattern()
+        };
+
+        for c in pattern.chars() {
+            match c {
+                '!' => w.write_str(self.opts.symbol())?,
+                '#' => currency_math::write_unsigned_decimal(
+                    w,
+                    rounded,
+                    precision,
+                    self.opts.grouping(),
+                    self.opts.separator(),
+                    self.opts.decimal(),
+                )?,
+                c => w.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// > Writes the current instance formatted with its options to an `io::Write`, without
+    /// > allocating
+    ///
+    /// Arguments:
+    ///
+    /// * `w`: The writer, e.g. a file or a `BufWriter`.
+    ///
+    /// Returns:
+    ///
+    /// The error of the writer, if any.
+    pub fn write_formatted_io<W: std::io::Write + ?Sized>(
+        &self,
+        w: &mut W,
+    ) -> std::io::Result<()> {
+        let mut adapter = IoAdapter {
+            inner: w,
+            error: None,
+        };
+
+        match self.write_formatted(&mut adapter) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(adapter
+                .error
+                .unwrap_or_else(|| std::io::Error::other("formatter error"))),
+        }
+    }
+
+    /// > Formats the current instance with the conventions of a bundled locale
+    ///
+    /// Arguments:
+    ///
+    /// * `tag`: The locale tag, e.g. `"de-CH"`, see `Locale::from_tag`.
+    ///
+    /// Returns:
+    ///
+    /// The formatted value, or `CurrencyErr::UnknownLocale` when the locale is not bundled.
+    pub fn format_locale(
+        &self,
+        tag: &str,
+    ) -> Result<String, CurrencyErr> {
+        Locale::from_tag(tag)
+            .map(|locale| self.format_with_locale(locale))
+            .ok_or_else(|| CurrencyErr::UnknownLocale(tag.to_string()))
+    }
+
+    /// > Formats the current instance with the conventions of a locale
+    ///
+    /// The decimal and group separators, grouping sizes, minus sign and symbol placement come
+    /// from the locale, while the precision, increment and rounding mode come from the options.
+    /// The currency of the locale is written with its narrow symbol (`$` rather than `US$`),
+    /// any other currency with the symbol of the options, spaced from the number when it ends
+    /// with a letter.
+    ///
+    /// Arguments:
+    ///
+    /// * `locale`: The locale to format with.
+    ///
+    /// Returns:
+    ///
+    /// The formatted value.
+    pub fn format_with_locale(
+        &self,
+        locale: &Locale,
+    ) -> String {
+        let (rounded, precision) = self.rounded_minor(None);
+
+        let currency = currency_math::to_decimal_string(rounded, precision).replacen('-', "", 1);
+
+        let (dollars, cents) = currency.split_once('.').unwrap_or((&currency, ""));
+
+        let mut number = locale.grouping().apply(dollars, locale.group());
+
+        if precision > 0 {
+            number = number + locale.decimal() + cents;
+        }
+
+        let symbol = match (self.opts.code(), IsoCurrency::from_code(locale.currency())) {
+            (Some(code), Some(iso)) if code == iso.code() => iso.narrow_symbol(),
+            _ => self.opts.symbol(),
+        };
+
+        let pattern = if rounded < 0 {
+            locale.negative_pattern()
+        } else {
+            locale.pattern()
+        };
+
+        let chars = pattern.chars().collect::<Vec<char>>();
+
+        let mut formatted = String::new();
+
+        for (i, c) in chars.iter().enumerate() {
+            match c {
+                '#' => formatted.push_str(&number),
+                '-' => formatted.push_str(locale.minus_sign()),
+                '¤' => {
+                    let before_number = chars.get(i + 1) == Some(&'#');
+                    let after_number = i > 0 && chars[i - 1] == '#';
+
+                    if after_number && symbol.starts_with(char::is_alphabetic) {
+                        formatted.push('\u{a0}');
+                    }
+
+                    formatted.push_str(symbol);
+
+                    if before_number && symbol.ends_with(char::is_alphabetic) {
+                        formatted.push('\u{a0}');
+                    }
+                }
+                c => formatted.push(*c),
+            }
+        }
+
+        formatted
+    }
+
+    /// It returns the cents of the value.
+    ///
+    /// Returns:
+    ///
+    /// The cents of the value.
+    pub fn cents(&self) -> u64 {
+        (self.minor_units() % Self::scale(self.precision())).unsigned_abs() as u64
+    }
+
+    /// It returns the value of the dollar.
+    ///
+    /// Returns:
+    ///
+    /// The value of the money object, saturating at `i64::MIN`/`i64::MAX`.
+    pub fn dollars(&self) -> i64 {
+        (self.minor_units() / Self::scale(self.precision()))
+            .clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    /// > It takes a number, converts it to minor units and adds it to the current minor units
+    ///
+    /// Arguments:
+    ///
+    /// * `number`: The number to add to the current value.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(
+        mut self,
+        number: f64,
+    ) -> Self {
+        self.add_mut(number);
+
+        self
+    }
+
+    /// It adds a number to the current instance in place.
+    pub(crate) fn add_mut(
+        &mut self,
+        number: f64,
+    ) {
+        self.int_value = self
+            .int_value
+            .add_minor(Self::parse(number, &self.opts), self.precision());
+    }
+
+    /// > It takes a number, converts it to minor units and adds it to the current minor units
+    ///
+    /// Arguments:
+    ///
+    /// * `number`: The number to add to the current value.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct.
+    pub fn add_string(
+        self,
+        number: &str,
+    ) -> Result<Self, CurrencyErr> {
+        let int_value = self
+            .int_value
+            .add_minor(Self::parse_string(number, &self.opts)?, self.precision());
+
+        Ok(Self::new(int_value, self.opts))
+    }
+
+    /// It adds the exact minor units of a currency object to the current instance in place,
+    /// whatever the `from_cents` option, saturating at the bounds of the backend.
+    pub(crate) fn add_exact_mut(
+        &mut self,
+        cur: &Self,
+    ) {
+        let rhs = self.saturating_minor_units_of(cur);
+
+        self.int_value = self.int_value.add_minor(rhs, self.precision());
+    }
+
+    /// It subtracts the exact minor units of a currency object from the current instance in
+    /// place, whatever the `from_cents` option, saturating at the bounds of the backend.
+    pub(crate) fn subtract_exact_mut(
+        &mut self,
+        cur: &Self,
+    ) {
+        let rhs = self.saturating_minor_units_of(cur);
+
+        self.int_value = self.int_value.sub_minor(rhs, self.precision());
+    }
+
+    /// It moves the exact value of a currency object to the precision of the current instance
+    /// as a backend amount, saturating when it does not fit.
+    fn saturating_minor_units_of(
+        &self,
+        cur: &Self,
+    ) -> B {
+        let minor = self.minor_units_of(cur).unwrap_or(if cur.is_negative() {
+            i128::MIN
+        } else {
+            i128::MAX
+        });
+
+        B::from_minor(minor, self.precision())
+    }
+
+    /// > It adds a currency object to the current instance, rejecting currencies of a different
+    /// > code
+    ///
+    /// A currency without a code can be added to any other currency.
+    ///
+    /// Arguments:
+    ///
+    /// * `cur`: The currency object to add to the current value.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct, `CurrencyErr::CurrencyMismatch` when both
+    /// currencies have a code and they differ, or `CurrencyErr::Overflow` when the sum does not
+    /// fit.
+    pub fn try_add(
+        self,
+        cur: Self,
+    ) -> Result<Self, CurrencyErr> {
+        self.try_add_cur(&cur)
+    }
+
+    /// It adds a currency object to the current instance, like `try_add` but borrowing it.
+    pub(crate) fn try_add_cur(
+        self,
+        cur: &Self,
+    ) -> Result<Self, CurrencyErr> {
+        self.check_code(cur)?;
+
+        let rhs = self.minor_units_of(cur)?;
+
+        let int_value = self.checked_op(|v, _| v.checked_add(rhs).ok_or(CurrencyErr::Overflow))?;
+
+        Ok(Self::new(int_value, self.opts))
+    }
+
+    /// > Subtracts a number from the current instance
+    ///
+    /// Arguments:
+    ///
+    /// * `number`: The number to subtract from the current instance.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct.
+    pub fn subtract(
+        mut self,
+        number: f64,
+    ) -> Self {
+        self.subtract_mut(number);
+
+        self
+    }
+
+    /// It subtracts a number from the current instance in place.
+    pub(crate) fn subtract_mut(
+        &mut self,
+        number: f64,
+    ) {
+        self.int_value = self
+            .int_value
+            .sub_minor(Self::parse(number, &self.opts), self.precision());
+    }
+
+    /// > Subtracts a currency object from the current instance, rejecting currencies of a
+    /// > different code
+    ///
+    /// A currency without a code can be subtracted from any other currency.
+    ///
+    /// Arguments:
+    ///
+    /// * `cur`: The currency object to subtract from the current instance.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct, `CurrencyErr::CurrencyMismatch` when both
+    /// currencies have a code and they differ, or `CurrencyErr::Overflow` when the difference
+    /// does not fit.
+    pub fn try_subtract(
+        self,
+        cur: Self,
+    ) -> Result<Self, CurrencyErr> {
+        self.check_code(&cur)?;
+
+        let rhs = self.minor_units_of(&cur)?;
+
+        let int_value = self.checked_op(|v, _| v.checked_sub(rhs).ok_or(CurrencyErr::Overflow))?;
+
+        Ok(Self::new(int_value, self.opts))
+    }
+
+    /// It checks that a currency object can be combined with the current instance.
+    ///
+    /// Arguments:
+    ///
+    /// * `cur`: The other currency object.
+    ///
+    /// Returns:
+    ///
+    /// `CurrencyErr::CurrencyMismatch` when both currencies have a code and they differ.
+    pub(crate) fn check_code<C: Backend>(
+        &self,
+        cur: &Currency<C>,
+    ) -> Result<(), CurrencyErr> {
+        match (self.opts.code(), cur.opts.code()) {
+            (Some(expected), Some(found)) if expected != found => {
+                Err(CurrencyErr::CurrencyMismatch {
+                    expected: expected.to_string(),
+                    found: found.to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// > Multiply the value of the current instance by the given number
+    ///
+    /// Arguments:
+    ///
+    /// * `number`: The number to multiply the Money object by.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct.
+    pub fn multiply(
+        mut self,
+        number: f64,
+    ) -> Self {
+        self.multiply_mut(number);
+
+        self
+    }
+
+    /// It multiplies the current instance by a number in place.
+    pub(crate) fn multiply_mut(
+        &mut self,
+        number: f64,
+    ) {
+        self.int_value =
+            self.int_value
+                .mul_f64(number, self.precision(), self.opts.rounding_mode());
+    }
+
+    /// It divides the number by the number passed in. Dividing by zero or NaN returns the
+    /// current instance unchanged, see `checked_div` to detect it.
+    ///
+    /// Arguments:
+    ///
+    /// * `number`: The number to divide by.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct.
+    pub fn divide(
+        mut self,
+        number: f64,
+    ) -> Self {
+        self.divide_mut(number);
+
+        self
+    }
+
+    /// It divides the current instance by a number in place, leaving it unchanged for zero or
+    /// NaN.
+    pub(crate) fn divide_mut(
+        &mut self,
+        number: f64,
+    ) {
+        if number != 0. && !number.is_nan() {
+            self.int_value =
+                self.int_value
+                    .div_f64(number, self.precision(), self.opts.rounding_mode());
+        }
+    }
+
+    /// > Adds a number to the current instance, reporting what the unchecked `add` would hide
+    ///
+    /// Arguments:
+    ///
+    /// * `number`: The number to add to the current value.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct, or `CurrencyErr::NonFiniteNumber` for NaN and
+    /// infinite numbers, `CurrencyErr::Overflow` when the sum does not fit the backend and
+    /// `CurrencyErr::PrecisionLoss` when the backend cannot hold the sum exactly.
+    pub fn checked_add(
+        self,
+        number: f64,
+    ) -> Result<Self, CurrencyErr> {
+        let int_value = self.checked_add_value(number)?;
+
+        Ok(Self::new(int_value, self.opts))
+    }
+
+    /// > Subtracts a number from the current instance, reporting what the unchecked `subtract`
+    /// > would hide
+    ///
+    /// Arguments:
+    ///
+    /// * `number`: The number to subtract from the current instance.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct, or the same errors as `checked_add`. A product too
+    /// small to show is rounded to zero, it is not an error.
+    pub fn checked_sub(
+        self,
+        number: f64,
+    ) -> Result<Self, CurrencyErr> {
+        let int_value = self.checked_sub_value(number)?;
+
+        Ok(Self::new(int_value, self.opts))
+    }
+
+    /// > Multiplies the current instance by a number, reporting what the unchecked `multiply`
+    /// > would hide
+    ///
+    /// Arguments:
+    ///
+    /// * `number`: The number to multiply the Money object by.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct, or the same errors as `checked_add`.
+    pub fn checked_mul(
+        self,
+        number: f64,
+    ) -> Result<Self, CurrencyErr> {
+        let int_value = self.checked_mul_value(number)?;
+
+        Ok(Self::new(int_value, self.opts))
+    }
+
+    /// > Divides the current instance by a number, reporting what the unchecked `divide` would
+    /// > hide
+    ///
+    /// Arguments:
+    ///
+    /// * `number`: The number to divide by.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct, `CurrencyErr::DivisionByZero` when `number` is zero,
+    /// or the same errors as `checked_add`. A quotient too small to show is rounded to zero, it
+    /// is not an error.
+    pub fn checked_div(
+        self,
+        number: f64,
+    ) -> Result<Self, CurrencyErr> {
+        let int_value = self.checked_div_value(number)?;
+
+        Ok(Self::new(int_value, self.opts))
+    }
+
+    /// > Adds a number to the current instance, saturating at the bounds of the backend
+    ///
+    /// An infinite number saturates in its direction and NaN leaves the value unchanged.
+    ///
+    /// Arguments:
+    ///
+    /// * `number`: The number to add to the current value.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct.
+    pub fn saturating_add(
+        self,
+        number: f64,
+    ) -> Self {
+        let result = self.checked_add_value(number);
+
+        let int_value = self.saturate(result, number, number < 0., |c| c.add(number));
+
+        Self::new(int_value, self.opts)
+    }
+
+    /// > Subtracts a number from the current instance, saturating at the bounds of the backend
+    ///
+    /// An infinite number saturates in its direction and NaN leaves the value unchanged.
+    ///
+    /// Arguments:
+    ///
+    /// * `number`: The number to subtract from the current instance.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct.
+    pub fn saturating_sub(
+        self,
+        number: f64,
+    ) -> Self {
+        let result = self.checked_sub_value(number);
+
+        let int_value = self.saturate(result, number, number > 0., |c| c.subtract(number));
+
+        Self::new(int_value, self.opts)
+    }
+
+    /// > Multiplies the current instance by a number, saturating at the bounds of the backend
+    ///
+    /// An infinite number saturates in the direction of the product and NaN leaves the value
+    /// unchanged.
+    ///
+    /// Arguments:
+    ///
+    /// * `number`: The number to multiply the Money object by.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct.
+    pub fn saturating_mul(
+        self,
+        number: f64,
+    ) -> Self {
+        let result = self.checked_mul_value(number);
+
+        let negative = (self.minor_units() < 0) != (number < 0.);
+
+        let int_value = self.saturate(result, number, negative, |c| c.multiply(number));
+
+        Self::new(int_value, self.opts)
+    }
+
+    /// > Divides the current instance by a number, saturating at the bounds of the backend
+    ///
+    /// Dividing by zero saturates in the direction of the current value, and NaN leaves the
+    /// value unchanged.
+    ///
+    /// Arguments:
+    ///
+    /// * `number`: The number to divide by.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct.
+    pub fn saturating_div(
+        self,
+        number: f64,
+    ) -> Self {
+        let result = self.checked_div_value(number);
+
+        let negative = (self.minor_units() < 0) != (number.is_sign_negative());
+
+        let int_value = self.saturate(result, number, negative, |c| c.divide(number));
+
+        Self::new(int_value, self.opts)
+    }
+
+    fn checked_add_value(
+        &self,
+        number: f64,
+    ) -> Result<B, CurrencyErr> {
+        let rhs = Self::checked_parse(number, &self.opts)?;
+
+        self.checked_op(|v, _| v.checked_add(rhs).ok_or(CurrencyErr::Overflow))
+    }
+
+    fn checked_sub_value(
+        &self,
+        number: f64,
+    ) -> Result<B, CurrencyErr> {
+        let rhs = Self::checked_parse(number, &self.opts)?;
+
+        self.checked_op(|v, _| v.checked_sub(rhs).ok_or(CurrencyErr::Overflow))
+    }
+
+    fn checked_mul_value(
+        &self,
+        number: f64,
+    ) -> Result<B, CurrencyErr> {
+        self.checked_op(|v, mode| currency_math::checked_mul_f64(v, number, mode))
+    }
+
+    fn checked_div_value(
+        &self,
+        number: f64,
+    ) -> Result<B, CurrencyErr> {
+        self.checked_op(|v, mode| currency_math::checked_div_f64(v, number, mode))
+    }
+
+    /// It applies an exact operation to the minor units of the current instance.
+    ///
+    /// Arguments:
+    ///
+    /// * `op`: The operation, given the minor units and the rounding mode.
+    ///
+    /// Returns:
+    ///
+    /// The backend amount of the result, or why it could not be computed.
+    pub(crate) fn checked_op(
+        &self,
+        op: impl FnOnce(i128, RoundingMode) -> Result<i128, CurrencyErr>,
+    ) -> Result<B, CurrencyErr> {
+        let precision = self.precision();
+
+        let minor_units = self.minor_units();
+
+        if B::try_from_minor(minor_units, precision)? != self.int_value {
+            return Err(CurrencyErr::Overflow);
+        }
+
+        B::try_from_minor(op(minor_units, self.opts.rounding_mode())?, precision)
+    }
+
+    /// It turns the result of a checked operation into a saturated amount.
+    ///
+    /// Arguments:
+    ///
+    /// * `result`: The result of the checked operation.
+    /// * `negative`: Whether the exact result is negative, which decides the bound to use.
+    /// * `unchecked`: The unchecked operation, used when only precision was lost.
+    ///
+    /// Returns:
+    ///
+    /// The backend amount.
+    fn saturate(
+        &self,
+        result: Result<B, CurrencyErr>,
+        number: f64,
+        negative: bool,
+        unchecked: impl FnOnce(Self) -> Self,
+    ) -> B {
+        let precision = self.precision();
+
+        let bound = if negative { i128::MIN } else { i128::MAX };
+
+        match result {
+            Ok(v) => v,
+            Err(CurrencyErr::PrecisionLoss) => unchecked(self.clone()).int_value,
+            Err(CurrencyErr::DivisionByZero) if self.minor_units() == 0 => self.int_value,
+            Err(CurrencyErr::NonFiniteNumber) if number.is_nan() || self.minor_units() == 0 => {
+                self.int_value
+            }
+            Err(
+                CurrencyErr::Overflow | CurrencyErr::DivisionByZero | CurrencyErr::NonFiniteNumber,
+            ) => B::from_minor(bound, precision),
+            Err(_) => self.int_value,
+        }
+    }
+
+    /// > Converts the current instance into another currency with a rate from the provider
+    ///
+    /// The result uses the ISO 4217 options of the target code, or the current options with the
+    /// target code when it is not an ISO code, and the rounding mode of the current instance.
+    ///
+    /// Arguments:
+    ///
+    /// * `code`: The code of the currency to convert into, e.g. `"EUR"`.
+    /// * `provider`: The source of the exchange rate.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct, or `CurrencyErr::MissingCode` when the current
+    /// instance has no code and `CurrencyErr::RateNotFound` when the provider has no rate.
+    pub fn convert_to<P: RateProvider + ?Sized>(
+        &self,
+        code: &str,
+        provider: &P,
+    ) -> Result<Self, CurrencyErr> {
+        let from = self.code().ok_or(CurrencyErr::MissingCode)?;
+
+        let rate = provider
+            .rate(from, code)
+            .ok_or_else(|| CurrencyErr::RateNotFound {
+                from: from.to_string(),
+                to: code.trim().to_ascii_uppercase(),
+            })?;
+
+        let opts = CurrencyOpts::for_code(code)
+            .unwrap_or_else(|| self.opts.clone())
+            .set_rounding_mode(self.opts.rounding_mode());
+
+        self.convert_with(&rate, Some(opts))
+    }
+
+    /// > Converts the current instance into another currency with the given rate
+    ///
+    /// The amount is rounded once, to the precision of the target options with their rounding
+    /// mode.
+    ///
+    /// Arguments:
+    ///
+    /// * `rate`: The exchange rate from the code of the current instance.
+    /// * `opts`: The options of the result, whose code is set to the target of the rate.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Money struct, or `CurrencyErr::MissingCode` when the current
+    /// instance has no code and `CurrencyErr::CurrencyMismatch` when it is not the source of the
+    /// rate or the options have another code.
+    pub fn convert_with(
+        &self,
+        rate: &ExchangeRate,
+        opts: Option<CurrencyOpts>,
+    ) -> Result<Self, CurrencyErr> {
+        let from = self.code().ok_or(CurrencyErr::MissingCode)?;
+
+        if from != rate.from() {
+            return Err(CurrencyErr::CurrencyMismatch {
+                expected: rate.from(),
+                found: from.to_string(),
+            });
+        }
+
+        let opts = opts.unwrap_or_default();
+
+        if let Some(found) = opts.code().filter(|c| *c != rate.to()) {
+            return Err(CurrencyErr::CurrencyMismatch {
+                expected: rate.to(),
+                found: found.to_string(),
+            });
+        }
+
+        let opts = opts.set_code(rate.to());
+
+        let minor = currency_math::mul_decimal(
+            self.minor_units(),
+            self.precision(),
+            rate.rate().mantissa(),
+            rate.rate().scale(),
+            opts.precision(),
+            opts.rounding_mode(),
+        )
+        .ok_or(CurrencyErr::Overflow)?;
+
+        let int_value = B::try_from_minor(minor, opts.precision())?;
+
+        Ok(Self::new(int_value, opts))
+    }
+
+    /// > This function takes a Money object and a count, and returns a vector of Money objects
+    /// > that are split evenly
+    ///
+    /// The parts are exact and always add up to the original value, so they do not depend on
+    /// the rounding mode. Leftover minor units go to the first parts.
+    ///
+    /// Arguments:
+    ///
+    /// * `count`: The number of items to distribute the money into.
+    ///
+    /// Returns:
+    ///
+    /// A vector of Money objects.
+    pub fn distribute(
+        self,
+        count: i64,
+    ) -> Vec<Self> {
+        if count <= 0 {
+            return vec![];
+        }
+
+        let precision = self.precision();
+
+        let int_value = self.minor_units();
+
+        let count = count as i128;
+
+        let split = int_value / count;
+
+        let pennies = int_value.unsigned_abs() - split.unsigned_abs() * count as u128;
+
+        let penny = if int_value >= 0 { 1 } else { -1 };
+
+        (0..count)
+            .map(|i| {
+                let item = if (i as u128) < pennies {
+                    split + penny
+                } else {
+                    split
+                };
+
+                Self::new(B::from_minor(item, precision), self.opts.clone())
+            })
+            .collect()
+    }
+
+    /// > This function takes a Money object and a list of ratios, and returns a vector of Money
+    /// > objects that are split proportionally
+    ///
+    /// The parts are exact and always add up to the original value. Leftover minor units go to
+    /// the parts with the largest remainders, the first ones on a tie, so `[70., 20., 10.]` and
+    /// `[0.7, 0.2, 0.1]` split the same way.
+    ///
+    /// Arguments:
+    ///
+    /// * `ratios`: The weights of the parts.
+    ///
+    /// Returns:
+    ///
+    /// A vector of Money objects, empty when a ratio is negative or not finite, or when the
+    /// ratios add up to zero.
+    pub fn allocate(
+        self,
+        ratios: &[f64],
+    ) -> Vec<Self> {
+        let precision = self.precision();
+
+        currency_math::allocate(self.minor_units(), ratios)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| Self::new(B::from_minor(item, precision), self.opts.clone()))
+            .collect()
+    }
+
+    /// `scale` returns the number of minor units in one major unit.
+    ///
+    /// Arguments:
+    ///
+    /// * `p`: The power to raise 10 to.
+    ///
+    /// Returns:
+    ///
+    /// The value of 10 to the power of p.
+    fn scale(p: u32) -> i128 {
+        currency_math::pow10(p).unwrap_or(i128::MAX)
+    }
+
+    /// `pow` takes a `u32` and returns a `f64`
+    ///
+    /// Arguments:
+    ///
+    /// * `p`: The power to raise 10 to.
+    ///
+    /// Returns:
+    ///
+    /// The value of 10 to the power of p.
+    fn pow(p: u32) -> f64 {
+        10_f64.powi(p as i32)
+    }
+}
+
+/// A `fmt::Write` over an `io::Write`, keeping the io error that `fmt::Error` cannot carry.
+struct IoAdapter<'a, W: std::io::Write + ?Sized> {
+    inner: &'a mut W,
+    error: Option<std::io::Error>,
+}
+
+impl<W: std::io::Write + ?Sized> std::fmt::Write for IoAdapter<'_, W> {
+    fn write_str(
+        &mut self,
+        s: &str,
+    ) -> std::fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            std::fmt::Error
+        })
     }
 }
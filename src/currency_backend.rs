@@ -0,0 +1,403 @@
+use crate::{currency_math, CurrencyErr, Decimal, DecimalErr, RoundingMode};
+use std::fmt::Debug;
+
+/// The numeric type a `Currency` stores its amount in.
+///
+/// The amount is always kept at the precision of the currency options, so a backend only needs
+/// to know how to hold minor units (e.g. cents) and how to do rounded arithmetic on them. Every
+/// rounding is done with the `RoundingMode` of the currency options.
+///
+/// * `i128`: exact minor units, the default.
+/// * `i64`: exact minor units in half the space, saturating at `i64::MIN`/`i64::MAX`.
+/// * `f64`: the legacy float behavior, fast but inexact above 2<sup>53</sup> minor units.
+/// * `Decimal`: exact fixed-point major units, which carry their own number of decimal places.
+pub trait Backend: Copy + Debug + Default + PartialEq + PartialOrd {
+    /// It creates an amount from a number of minor units.
+    ///
+    /// Arguments:
+    ///
+    /// * `value`: The minor units, saturating when they do not fit.
+    /// * `scale`: The number of decimal places of the minor units.
+    fn from_minor(
+        value: i128,
+        scale: u32,
+    ) -> Self;
+
+    /// It creates an amount from a number of minor units, failing instead of saturating.
+    ///
+    /// Arguments:
+    ///
+    /// * `value`: The minor units.
+    /// * `scale`: The number of decimal places of the minor units.
+    ///
+    /// Returns:
+    ///
+    /// `CurrencyErr::Overflow` when the minor units do not fit in the backend.
+    fn try_from_minor(
+        value: i128,
+        scale: u32,
+    ) -> Result<Self, CurrencyErr> {
+        let v = Self::from_minor(value, scale);
+
+        if v.to_minor(scale) == value {
+            Ok(v)
+        } else {
+            Err(CurrencyErr::Overflow)
+        }
+    }
+
+    /// It returns the amount as a number of minor units.
+    ///
+    /// Arguments:
+    ///
+    /// * `scale`: The number of decimal places of the minor units.
+    fn to_minor(
+        self,
+        scale: u32,
+    ) -> i128;
+
+    /// It returns the amount as a float number of minor units.
+    ///
+    /// Arguments:
+    ///
+    /// * `scale`: The number of decimal places of the minor units.
+    fn to_f64(
+        self,
+        scale: u32,
+    ) -> f64 {
+        self.to_minor(scale) as f64
+    }
+
+    /// It converts a float into an amount at the given number of decimal places, rounded to a
+    /// minor unit.
+    ///
+    /// Arguments:
+    ///
+    /// * `value`: The value to be parsed.
+    /// * `scale`: The number of decimal places of the amount.
+    /// * `mode`: The rounding mode.
+    fn parse(
+        value: f64,
+        scale: u32,
+        mode: RoundingMode,
+    ) -> Self {
+        Self::from_minor(currency_math::scale_f64(value, scale, mode), scale)
+    }
+
+    /// It converts a plain decimal string (`-123.456`) into an amount at the given number of
+    /// decimal places, rounded to a minor unit.
+    ///
+    /// Arguments:
+    ///
+    /// * `value`: The string value to be parsed.
+    /// * `scale`: The number of decimal places of the amount.
+    /// * `mode`: The rounding mode.
+    ///
+    /// Returns:
+    ///
+    /// `None` when the string is not a decimal number.
+    fn parse_string(
+        value: &str,
+        scale: u32,
+        mode: RoundingMode,
+    ) -> Option<Self> {
+        match currency_math::parse_decimal(value, scale, mode) {
+            Ok(v) => Some(Self::from_minor(v, scale)),
+            Err(DecimalErr::Overflow { negative: true }) => {
+                Some(Self::from_minor(i128::MIN, scale))
+            }
+            Err(DecimalErr::Overflow { negative: false }) => {
+                Some(Self::from_minor(i128::MAX, scale))
+            }
+            Err(DecimalErr::Invalid) => None,
+        }
+    }
+
+    /// It moves an amount from one number of decimal places to another, rounding to a minor unit
+    /// when places are dropped.
+    ///
+    /// Arguments:
+    ///
+    /// * `from`: The number of decimal places of the amount.
+    /// * `to`: The number of decimal places wanted.
+    /// * `mode`: The rounding mode.
+    fn round_dp(
+        self,
+        from: u32,
+        to: u32,
+        mode: RoundingMode,
+    ) -> Self {
+        let value = self.to_minor(from);
+
+        let rescaled = currency_math::rescale(value, from, to, mode).unwrap_or(if value < 0 {
+            i128::MIN
+        } else {
+            i128::MAX
+        });
+
+        Self::from_minor(rescaled, to)
+    }
+
+    /// It rounds an amount to a multiple of the increment.
+    ///
+    /// Arguments:
+    ///
+    /// * `increment`: The mantissa and scale of the value to round to, in major units, or
+    ///   `None` to round to one minor unit.
+    /// * `scale`: The number of decimal places of the amount.
+    /// * `mode`: The rounding mode.
+    fn rounding(
+        self,
+        increment: Option<(i128, u32)>,
+        scale: u32,
+        mode: RoundingMode,
+    ) -> Self {
+        match increment {
+            Some(increment) => Self::from_minor(
+                currency_math::round_to_increment(self.to_minor(scale), increment, scale, mode),
+                scale,
+            ),
+            None => self,
+        }
+    }
+
+    /// It adds two amounts of the same number of decimal places.
+    fn add_minor(
+        self,
+        rhs: Self,
+        scale: u32,
+    ) -> Self {
+        Self::from_minor(
+            self.to_minor(scale).saturating_add(rhs.to_minor(scale)),
+            scale,
+        )
+    }
+
+    /// It subtracts two amounts of the same number of decimal places.
+    fn sub_minor(
+        self,
+        rhs: Self,
+        scale: u32,
+    ) -> Self {
+        Self::from_minor(
+            self.to_minor(scale).saturating_sub(rhs.to_minor(scale)),
+            scale,
+        )
+    }
+
+    /// It multiplies an amount by a number, rounding to a minor unit.
+    fn mul_f64(
+        self,
+        number: f64,
+        scale: u32,
+        mode: RoundingMode,
+    ) -> Self {
+        Self::from_minor(
+            currency_math::mul_f64(self.to_minor(scale), number, mode),
+            scale,
+        )
+    }
+
+    /// It divides an amount by a non-zero number, rounding to a minor unit.
+    fn div_f64(
+        self,
+        number: f64,
+        scale: u32,
+        mode: RoundingMode,
+    ) -> Self {
+        Self::from_minor(
+            currency_math::div_f64(self.to_minor(scale), number, mode),
+            scale,
+        )
+    }
+}
+
+impl Backend for i128 {
+    fn from_minor(
+        value: i128,
+        _scale: u32,
+    ) -> Self {
+        value
+    }
+
+    fn to_minor(
+        self,
+        _scale: u32,
+    ) -> i128 {
+        self
+    }
+}
+
+impl Backend for i64 {
+    fn from_minor(
+        value: i128,
+        _scale: u32,
+    ) -> Self {
+        value.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    fn to_minor(
+        self,
+        _scale: u32,
+    ) -> i128 {
+        self as i128
+    }
+
+    fn add_minor(
+        self,
+        rhs: Self,
+        _scale: u32,
+    ) -> Self {
+        self.saturating_add(rhs)
+    }
+
+    fn sub_minor(
+        self,
+        rhs: Self,
+        _scale: u32,
+    ) -> Self {
+        self.saturating_sub(rhs)
+    }
+}
+
+impl Backend for f64 {
+    fn from_minor(
+        value: i128,
+        _scale: u32,
+    ) -> Self {
+        value as f64
+    }
+
+    fn to_minor(
+        self,
+        _scale: u32,
+    ) -> i128 {
+        self as i128
+    }
+
+    fn to_f64(
+        self,
+        _scale: u32,
+    ) -> f64 {
+        self
+    }
+
+    fn try_from_minor(
+        value: i128,
+        _scale: u32,
+    ) -> Result<Self, CurrencyErr> {
+        if value.unsigned_abs() > MAX_SAFE_INTEGER {
+            Err(CurrencyErr::PrecisionLoss)
+        } else {
+            Ok(value as f64)
+        }
+    }
+
+    fn parse(
+        value: f64,
+        scale: u32,
+        mode: RoundingMode,
+    ) -> Self {
+        round_f64(value * pow_f64(scale), mode)
+    }
+
+    fn round_dp(
+        self,
+        from: u32,
+        to: u32,
+        mode: RoundingMode,
+    ) -> Self {
+        round_f64(self * pow_f64(to) / pow_f64(from), mode)
+    }
+
+    fn rounding(
+        self,
+        increment: Option<(i128, u32)>,
+        scale: u32,
+        mode: RoundingMode,
+    ) -> Self {
+        let increment = match increment {
+            Some((m, s)) => m as f64 / pow_f64(s) * pow_f64(scale),
+            None => 1.,
+        };
+
+        round_f64(self / increment, mode) * increment
+    }
+
+    fn add_minor(
+        self,
+        rhs: Self,
+        _scale: u32,
+    ) -> Self {
+        self + rhs
+    }
+
+    fn sub_minor(
+        self,
+        rhs: Self,
+        _scale: u32,
+    ) -> Self {
+        self - rhs
+    }
+
+    fn mul_f64(
+        self,
+        number: f64,
+        _scale: u32,
+        mode: RoundingMode,
+    ) -> Self {
+        round_f64(self * number, mode)
+    }
+
+    fn div_f64(
+        self,
+        number: f64,
+        _scale: u32,
+        mode: RoundingMode,
+    ) -> Self {
+        round_f64(self / number, mode)
+    }
+}
+
+impl Backend for Decimal {
+    fn from_minor(
+        value: i128,
+        scale: u32,
+    ) -> Self {
+        Decimal::new(value, scale)
+    }
+
+    fn to_minor(
+        self,
+        scale: u32,
+    ) -> i128 {
+        match self.rescale(scale) {
+            Some(v) => v.mantissa(),
+            None if self.mantissa() < 0 => i128::MIN,
+            None => i128::MAX,
+        }
+    }
+}
+
+/// The largest integer a float holds exactly, 2<sup>53</sup>.
+const MAX_SAFE_INTEGER: u128 = 1 << 53;
+
+/// It rounds a float number of minor units the way the legacy float backend always has: first to
+/// four decimal places to drop representation noise, then to an integer.
+fn round_f64(
+    v: f64,
+    mode: RoundingMode,
+) -> f64 {
+    let snapped = (v * 10_000.).round() / 10_000.;
+
+    if snapped.is_finite() {
+        mode.round_f64(snapped)
+    } else {
+        mode.round_f64(v)
+    }
+}
+
+/// `pow_f64` takes a `u32` and returns a `f64`
+fn pow_f64(p: u32) -> f64 {
+    10_f64.powi(p as i32)
+}
@@ -0,0 +1,271 @@
+use crate::{Backend, Currency, CurrencyErr, CurrencyOpts, RateProvider};
+use std::collections::BTreeMap;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+/// A sum of amounts in several currencies, one amount per currency code.
+///
+/// Amounts of the same code are added together with the options of the first one added, and
+/// amounts without a code are kept together under no code. Iteration is ordered by code.
+#[derive(Debug, Clone)]
+pub struct MoneyBag<B: Backend = i128> {
+    amounts: BTreeMap<Option<String>, Currency<B>>,
+}
+
+impl<B: Backend> Default for MoneyBag<B> {
+    fn default() -> Self {
+        Self {
+            amounts: BTreeMap::new(),
+        }
+    }
+}
+
+impl<B: Backend> MoneyBag<B> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// > Adds a currency object to the amount of its code
+    ///
+    /// Arguments:
+    ///
+    /// * `cur`: The currency object to add.
+    ///
+    /// Returns:
+    ///
+    /// The bag with the currency added.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(
+        mut self,
+        cur: Currency<B>,
+    ) -> Self {
+        let mut amount = self.take(&cur);
+
+        amount.add_exact_mut(&cur);
+        self.amounts.insert(cur.code().map(str::to_string), amount);
+        self
+    }
+
+    /// > Subtracts a currency object from the amount of its code
+    ///
+    /// Arguments:
+    ///
+    /// * `cur`: The currency object to subtract.
+    ///
+    /// Returns:
+    ///
+    /// The bag with the currency subtracted.
+    pub fn subtract(
+        mut self,
+        cur: Currency<B>,
+    ) -> Self {
+        let mut amount = self.take(&cur);
+
+        amount.subtract_exact_mut(&cur);
+        self.amounts.insert(cur.code().map(str::to_string), amount);
+        self
+    }
+
+    /// > Converts every amount into one currency with rates from the provider and adds them up
+    ///
+    /// Amounts already in the currency, and the amount without a code, are added as they are.
+    ///
+    /// Arguments:
+    ///
+    /// * `code`: The code of the currency to collapse into, e.g. `"EUR"`.
+    /// * `provider`: The source of the exchange rates.
+    ///
+    /// Returns:
+    ///
+    /// The total, or the error of the first amount that could not be converted or added, see
+    /// `Currency::convert_to`.
+    pub fn collapse<P: RateProvider + ?Sized>(
+        &self,
+        code: &str,
+        provider: &P,
+    ) -> Result<Currency<B>, CurrencyErr> {
+        let opts =
+            CurrencyOpts::for_code(code).unwrap_or_else(|| CurrencyOpts::new().set_code(code));
+
+        let mut total = zero(&opts);
+
+        for amount in self.amounts.values() {
+            let converted = if amount.code().is_none() || amount.code() == opts.code() {
+                amount.clone()
+            } else {
+                amount.convert_to(code, provider)?
+            };
+
+            total = total.try_add(converted)?;
+        }
+
+        Ok(total)
+    }
+
+    /// It removes the amount of the code of a currency object, or returns zero in its options.
+    fn take(
+        &mut self,
+        cur: &Currency<B>,
+    ) -> Currency<B> {
+        self.amounts
+            .remove(&cur.code().map(str::to_string))
+            .unwrap_or_else(|| zero(&cur.opts))
+    }
+}
+
+// GETTERS
+impl<B: Backend> MoneyBag<B> {
+    /// The amount of a currency code, `None` when nothing of it was added.
+    pub fn get(
+        &self,
+        code: &str,
+    ) -> Option<&Currency<B>> {
+        self.amounts.get(&Some(code.trim().to_ascii_uppercase()))
+    }
+
+    /// The codes of the amounts, without the amount that has no code.
+    pub fn codes(&self) -> Vec<String> {
+        self.amounts.keys().flatten().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.amounts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.amounts.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Currency<B>> {
+        self.amounts.values()
+    }
+}
+
+/// It creates a zero amount in the given options.
+fn zero<B: Backend>(opts: &CurrencyOpts) -> Currency<B> {
+    Currency::new(B::from_minor(0, opts.precision()), opts.clone())
+}
+
+impl<B: Backend> Add<Currency<B>> for MoneyBag<B> {
+    type Output = MoneyBag<B>;
+
+    fn add(
+        self,
+        rhs: Currency<B>,
+    ) -> Self::Output {
+        self.add(rhs)
+    }
+}
+
+impl<B: Backend> Add for MoneyBag<B> {
+    type Output = MoneyBag<B>;
+
+    fn add(
+        self,
+        rhs: Self,
+    ) -> Self::Output {
+        rhs.into_iter().fold(self, MoneyBag::add)
+    }
+}
+
+impl<B: Backend> Sub<Currency<B>> for MoneyBag<B> {
+    type Output = MoneyBag<B>;
+
+    fn sub(
+        self,
+        rhs: Currency<B>,
+    ) -> Self::Output {
+        self.subtract(rhs)
+    }
+}
+
+impl<B: Backend> Sub for MoneyBag<B> {
+    type Output = MoneyBag<B>;
+
+    fn sub(
+        self,
+        rhs: Self,
+    ) -> Self::Output {
+        rhs.into_iter().fold(self, MoneyBag::subtract)
+    }
+}
+
+impl<B: Backend> Neg for MoneyBag<B> {
+    type Output = MoneyBag<B>;
+
+    fn neg(self) -> Self::Output {
+        self.into_iter().fold(Self::new(), MoneyBag::subtract)
+    }
+}
+
+// Assign
+
+impl<B: Backend> AddAssign<Currency<B>> for MoneyBag<B> {
+    fn add_assign(
+        &mut self,
+        rhs: Currency<B>,
+    ) {
+        *self = std::mem::take(self).add(rhs)
+    }
+}
+
+impl<B: Backend> AddAssign for MoneyBag<B> {
+    fn add_assign(
+        &mut self,
+        rhs: Self,
+    ) {
+        *self = std::mem::take(self) + rhs
+    }
+}
+
+impl<B: Backend> SubAssign<Currency<B>> for MoneyBag<B> {
+    fn sub_assign(
+        &mut self,
+        rhs: Currency<B>,
+    ) {
+        *self = std::mem::take(self).subtract(rhs)
+    }
+}
+
+impl<B: Backend> SubAssign for MoneyBag<B> {
+    fn sub_assign(
+        &mut self,
+        rhs: Self,
+    ) {
+        *self = std::mem::take(self) - rhs
+    }
+}
+
+// Iteration
+
+impl<B: Backend> FromIterator<Currency<B>> for MoneyBag<B> {
+    fn from_iter<I: IntoIterator<Item = Currency<B>>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::new(), MoneyBag::add)
+    }
+}
+
+impl<B: Backend> Extend<Currency<B>> for MoneyBag<B> {
+    fn extend<I: IntoIterator<Item = Currency<B>>>(
+        &mut self,
+        iter: I,
+    ) {
+        *self = iter.into_iter().fold(std::mem::take(self), MoneyBag::add)
+    }
+}
+
+impl<B: Backend> IntoIterator for MoneyBag<B> {
+    type Item = Currency<B>;
+    type IntoIter = std::collections::btree_map::IntoValues<Option<String>, Currency<B>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.amounts.into_values()
+    }
+}
+
+impl<'a, B: Backend> IntoIterator for &'a MoneyBag<B> {
+    type Item = &'a Currency<B>;
+    type IntoIter = std::collections::btree_map::Values<'a, Option<String>, Currency<B>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.amounts.values()
+    }
+}
@@ -0,0 +1,194 @@
+use crate::{currency_math, CurrencyErr, DecimalErr, ParseErrKind, ParseError, RoundingMode};
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// A fixed-point decimal number made of an integer mantissa and a number of decimal places.
+///
+/// `Decimal::new(12345, 2)` is the exact value `123.45`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    /// It creates a new decimal from a mantissa and a number of decimal places.
+    ///
+    /// Arguments:
+    ///
+    /// * `mantissa`: The digits of the decimal.
+    /// * `scale`: The number of decimal places of `mantissa`.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Decimal struct.
+    pub fn new(
+        mantissa: i128,
+        scale: u32,
+    ) -> Self {
+        Self { mantissa, scale }
+    }
+
+    /// It creates a decimal from the shortest round-trip representation of a float, so `0.1`
+    /// becomes exactly `0.1`.
+    ///
+    /// Arguments:
+    ///
+    /// * `value`: The float to convert.
+    ///
+    /// Returns:
+    ///
+    /// `None` when the value is not finite or does not fit.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        currency_math::decimal_from_f64(value).map(|(mantissa, scale)| Self { mantissa, scale })
+    }
+
+    /// It returns the digits of the decimal.
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    /// It returns the number of decimal places of the decimal.
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// It returns the closest float to the decimal.
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap_or(0.)
+    }
+
+    /// It moves the decimal to another number of decimal places, rounding half away from zero
+    /// when digits are dropped.
+    ///
+    /// Arguments:
+    ///
+    /// * `scale`: The number of decimal places wanted.
+    ///
+    /// Returns:
+    ///
+    /// `None` when the result does not fit.
+    pub fn rescale(
+        &self,
+        scale: u32,
+    ) -> Option<Self> {
+        self.round(scale, RoundingMode::default())
+    }
+
+    /// It moves the decimal to another number of decimal places, rounding with the given mode
+    /// when digits are dropped.
+    ///
+    /// Arguments:
+    ///
+    /// * `scale`: The number of decimal places wanted.
+    /// * `mode`: The rounding mode.
+    ///
+    /// Returns:
+    ///
+    /// `None` when the result does not fit.
+    pub fn round(
+        &self,
+        scale: u32,
+        mode: RoundingMode,
+    ) -> Option<Self> {
+        currency_math::rescale(self.mantissa, self.scale, scale, mode)
+            .map(|mantissa| Self { mantissa, scale })
+    }
+
+    /// It removes the trailing zeros of the decimal places, so `1.500` becomes `1.5`.
+    pub fn normalize(&self) -> Self {
+        let mut mantissa = self.mantissa;
+        let mut scale = self.scale;
+
+        while scale > 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            scale -= 1;
+        }
+
+        Self { mantissa, scale }
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            currency_math::to_decimal_string(self.mantissa, self.scale)
+        )
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = CurrencyErr;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let scale = value.split_once('.').map_or(0, |(_, frac)| frac.len()) as u32;
+
+        match currency_math::parse_decimal(value, scale, RoundingMode::default()) {
+            Ok(mantissa) => Ok(Self { mantissa, scale }),
+            Err(DecimalErr::Invalid) => Err(CurrencyErr::invalid(format!(
+                "invalid decimal value: {value:?}"
+            ))),
+            Err(DecimalErr::Overflow { .. }) => Err(CurrencyErr::ParseErr(ParseError::new(
+                ParseErrKind::Overflow,
+                None,
+                format!("decimal value out of range: {value:?}"),
+            ))),
+        }
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(
+        &self,
+        other: &Self,
+    ) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(
+        &self,
+        other: &Self,
+    ) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(
+        &self,
+        other: &Self,
+    ) -> Ordering {
+        let scale = self.scale.max(other.scale);
+
+        // A mantissa that overflows when moved to the larger scale is larger in magnitude than
+        // any mantissa at that scale, so its sign decides.
+        match (self.rescale(scale), other.rescale(scale)) {
+            (Some(a), Some(b)) => a.mantissa.cmp(&b.mantissa),
+            (None, _) => self.mantissa.cmp(&0),
+            (_, None) => 0.cmp(&other.mantissa),
+        }
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(
+        &self,
+        state: &mut H,
+    ) {
+        let normalized = self.normalize();
+
+        normalized.mantissa.hash(state);
+        normalized.scale.hash(state);
+    }
+}
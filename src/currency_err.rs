@@ -1,4 +1,196 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::sync::Arc;
+
+/// The errors of the fallible operations of the crate.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CurrencyErr {
-    ParseErr(String),
+    /// A string is not a valid amount, see `ParseError` for the kind and position.
+    ParseErr(ParseError),
+    /// Two amounts of different currency codes were combined.
+    CurrencyMismatch { expected: String, found: String },
+    /// The result does not fit in the backend.
+    Overflow,
+    /// An amount was divided by zero.
+    DivisionByZero,
+    /// A number is NaN or infinite.
+    NonFiniteNumber,
+    /// The backend cannot hold the result exactly.
+    PrecisionLoss,
+    /// An amount without a currency code was converted.
+    MissingCode,
+    /// The rate provider has no rate between two currencies.
+    RateNotFound { from: String, to: String },
+    /// An exchange rate is not greater than zero.
+    InvalidRate,
+    /// A rate file could not be read.
+    Io {
+        path: Option<String>,
+        source: IoError,
+    },
+    /// A locale is not bundled.
+    UnknownLocale(String),
+    /// The currency options are inconsistent, e.g. the same decimal and group separator.
+    InvalidOptions(String),
+    /// The thresholds of a tax schedule are not in ascending order.
+    InvalidSchedule(String),
+}
+
+impl CurrencyErr {
+    /// It creates a parse error without a position.
+    pub(crate) fn invalid(message: impl Into<String>) -> Self {
+        Self::ParseErr(ParseError::new(ParseErrKind::Invalid, None, message))
+    }
+
+    /// It creates an error for a failed read.
+    pub(crate) fn io(
+        path: Option<String>,
+        error: std::io::Error,
+    ) -> Self {
+        Self::Io {
+            path,
+            source: IoError(Arc::new(error)),
+        }
+    }
+}
+
+impl Display for CurrencyErr {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Self::ParseErr(_) => write!(f, "could not parse the value"),
+            Self::CurrencyMismatch { expected, found } => {
+                write!(f, "currency mismatch: expected {expected}, found {found}")
+            }
+            Self::Overflow => write!(f, "amount out of range"),
+            Self::DivisionByZero => write!(f, "division by zero"),
+            Self::NonFiniteNumber => write!(f, "number is not finite"),
+            Self::PrecisionLoss => write!(f, "amount cannot be held exactly by the backend"),
+            Self::MissingCode => write!(f, "currency has no code"),
+            Self::RateNotFound { from, to } => write!(f, "no exchange rate from {from} to {to}"),
+            Self::InvalidRate => write!(f, "exchange rate must be greater than zero"),
+            Self::Io {
+                path: Some(path), ..
+            } => write!(f, "could not read {path}"),
+            Self::Io { path: None, .. } => write!(f, "could not read the rates"),
+            Self::UnknownLocale(tag) => write!(f, "unknown locale {tag:?}"),
+            Self::InvalidOptions(message) => write!(f, "invalid currency options: {message}"),
+            Self::InvalidSchedule(message) => write!(f, "invalid tax schedule: {message}"),
+        }
+    }
+}
+
+impl Error for CurrencyErr {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::ParseErr(e) => Some(e),
+            Self::Io { source, .. } => Some(source.0.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// A shared `std::io::Error`, so that `CurrencyErr` stays `Clone`. Two errors are equal when
+/// they have the same kind and message.
+#[derive(Debug, Clone)]
+pub struct IoError(pub(crate) Arc<std::io::Error>);
+
+impl IoError {
+    pub fn kind(&self) -> std::io::ErrorKind {
+        self.0.kind()
+    }
+
+    pub fn inner(&self) -> &std::io::Error {
+        &self.0
+    }
+}
+
+impl PartialEq for IoError {
+    fn eq(
+        &self,
+        other: &Self,
+    ) -> bool {
+        self.kind() == other.kind() && self.0.to_string() == other.0.to_string()
+    }
+}
+
+/// Why a string could not be parsed.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParseError {
+    kind: ParseErrKind,
+    offset: Option<usize>,
+    message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(
+        kind: ParseErrKind,
+        offset: Option<usize>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            offset,
+            message: message.into(),
+        }
+    }
+}
+
+// GETTERS
+impl ParseError {
+    pub fn kind(&self) -> ParseErrKind {
+        self.kind
+    }
+
+    /// The byte offset in the input where the violation was found, when it is known.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    pub fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// The kind of violation a string was rejected for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParseErrKind {
+    /// The input has no amount.
+    Empty,
+    /// A character that is not part of the grammar.
+    UnexpectedChar,
+    /// A second sign, or a sign after the number or inside parentheses.
+    MisplacedSign,
+    /// A group separator that does not match the grouping of the options.
+    InvalidGroup,
+    /// A second decimal separator.
+    MultipleDecimals,
+    /// A decimal separator without digits before or after it.
+    MissingDigits,
+    /// An opening parenthesis without a closing one.
+    UnbalancedParenthesis,
+    /// A currency code other than the one of the options.
+    CodeMismatch,
+    /// A three letter code that is not an ISO 4217 code.
+    UnknownCode,
+    /// An amount that does not fit in the backend.
+    Overflow,
+    /// Any other invalid input.
+    Invalid,
 }
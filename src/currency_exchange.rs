@@ -0,0 +1,248 @@
+use crate::{currency_math, CurrencyErr, Decimal, RoundingMode};
+use std::collections::HashMap;
+
+/// The number of decimal places kept when a rate has to be derived from others, by inverting it
+/// or by triangulating it through a base currency.
+const RATE_SCALE: u32 = 18;
+
+/// The price of one unit of a currency in another one.
+///
+/// `ExchangeRate::new("USD", "EUR", "0.92".parse()?)` means that 1 USD buys 0.92 EUR.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExchangeRate {
+    from: String,
+    to: String,
+    rate: Decimal,
+    via: Option<String>,
+}
+
+impl ExchangeRate {
+    /// It creates a new exchange rate between two currency codes.
+    ///
+    /// Arguments:
+    ///
+    /// * `from`: The code of the currency being converted, e.g. `"USD"`.
+    /// * `to`: The code of the currency converted into, e.g. `"EUR"`.
+    /// * `rate`: The number of `to` units one `from` unit buys.
+    ///
+    /// Returns:
+    ///
+    /// `CurrencyErr::InvalidRate` when the rate is not greater than zero.
+    pub fn new(
+        from: &str,
+        to: &str,
+        rate: Decimal,
+    ) -> Result<Self, CurrencyErr> {
+        if rate.mantissa() <= 0 {
+            return Err(CurrencyErr::InvalidRate);
+        }
+
+        Ok(Self {
+            from: normalize_code(from),
+            to: normalize_code(to),
+            rate,
+            via: None,
+        })
+    }
+
+    /// It returns the rate that converts `from` into `to` and back, rounded to 18 decimal places.
+    ///
+    /// Returns:
+    ///
+    /// `None` when the inverse does not fit.
+    pub fn inverse(&self) -> Option<Self> {
+        let one = currency_math::pow10(RATE_SCALE)?;
+        let d = currency_math::pow10(self.rate.scale())?;
+
+        let mantissa =
+            currency_math::mul_div_round(one, d, self.rate.mantissa(), RoundingMode::default())?;
+
+        Some(Self {
+            from: self.to.clone(),
+            to: self.from.clone(),
+            rate: Decimal::new(mantissa, RATE_SCALE).normalize(),
+            via: self.via.clone(),
+        })
+        .filter(|r| r.rate.mantissa() > 0)
+    }
+
+    /// It chains two rates, `from -> x` and `x -> to`, into a rate `from -> to` that remembers
+    /// it went through `x`.
+    ///
+    /// Arguments:
+    ///
+    /// * `next`: The rate from the target of the current rate.
+    ///
+    /// Returns:
+    ///
+    /// `None` when the rates do not chain or the product does not fit.
+    pub fn then(
+        &self,
+        next: &ExchangeRate,
+    ) -> Option<Self> {
+        if self.to != next.from {
+            return None;
+        }
+
+        let scale = (self.rate.scale() + next.rate.scale()).min(RATE_SCALE);
+
+        let mantissa = currency_math::mul_decimal(
+            self.rate.mantissa(),
+            self.rate.scale(),
+            next.rate.mantissa(),
+            next.rate.scale(),
+            scale,
+            RoundingMode::default(),
+        )?;
+
+        Some(Self {
+            from: self.from.clone(),
+            to: next.to.clone(),
+            rate: Decimal::new(mantissa, scale).normalize(),
+            via: Some(self.to.clone()),
+        })
+        .filter(|r| r.rate.mantissa() > 0)
+    }
+}
+
+// GETTERS
+impl ExchangeRate {
+    pub fn from(&self) -> String {
+        self.from.clone()
+    }
+
+    pub fn to(&self) -> String {
+        self.to.clone()
+    }
+
+    pub fn rate(&self) -> Decimal {
+        self.rate
+    }
+
+    /// The base currency the rate was triangulated through, if any.
+    pub fn via(&self) -> Option<String> {
+        self.via.clone()
+    }
+}
+
+/// A source of exchange rates.
+pub trait RateProvider {
+    /// It looks up the rate to convert one currency into another.
+    ///
+    /// Arguments:
+    ///
+    /// * `from`: The code of the currency being converted.
+    /// * `to`: The code of the currency converted into.
+    ///
+    /// Returns:
+    ///
+    /// The rate, or `None` when the provider does not know it.
+    fn rate(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Option<ExchangeRate>;
+}
+
+/// An in-memory table of exchange rates.
+///
+/// A rate is found directly, by inverting the opposite rate, or by triangulating through the
+/// base currency when one is set.
+#[derive(Debug, Clone, Default)]
+pub struct RateTable {
+    base: Option<String>,
+    rates: HashMap<(String, String), ExchangeRate>,
+}
+
+impl RateTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// It adds a rate to the table, replacing the one between the same currencies.
+    ///
+    /// Arguments:
+    ///
+    /// * `rate`: The rate to add.
+    pub fn insert(
+        &mut self,
+        rate: ExchangeRate,
+    ) {
+        self.rates
+            .insert((rate.from.clone(), rate.to.clone()), rate);
+    }
+
+    /// It looks up a rate stored in either direction.
+    fn direct(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Option<ExchangeRate> {
+        if from == to {
+            return Some(ExchangeRate {
+                from: from.to_string(),
+                to: to.to_string(),
+                rate: Decimal::new(1, 0),
+                via: None,
+            });
+        }
+
+        match self.rates.get(&(from.to_string(), to.to_string())) {
+            Some(rate) => Some(rate.clone()),
+            None => self
+                .rates
+                .get(&(to.to_string(), from.to_string()))
+                .and_then(ExchangeRate::inverse),
+        }
+    }
+}
+
+// SETTERS
+impl RateTable {
+    /// The currency rates are triangulated through when there is no direct rate.
+    pub fn set_base(
+        mut self,
+        base: &str,
+    ) -> Self {
+        self.base = Some(normalize_code(base));
+        self
+    }
+
+    pub fn set_rate(
+        mut self,
+        rate: ExchangeRate,
+    ) -> Self {
+        self.insert(rate);
+        self
+    }
+}
+
+// GETTERS
+impl RateTable {
+    pub fn base(&self) -> Option<String> {
+        self.base.clone()
+    }
+}
+
+impl RateProvider for RateTable {
+    fn rate(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Option<ExchangeRate> {
+        let (from, to) = (normalize_code(from), normalize_code(to));
+
+        if let Some(rate) = self.direct(&from, &to) {
+            return Some(rate);
+        }
+
+        let base = self.base.as_ref()?;
+
+        self.direct(&from, base)?.then(&self.direct(base, &to)?)
+    }
+}
+
+/// It brings a currency code to the upper case form codes are compared in.
+fn normalize_code(code: &str) -> String {
+    code.trim().to_ascii_uppercase()
+}
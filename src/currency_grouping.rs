@@ -0,0 +1,131 @@
+/// How the integer digits of a value are split into groups, e.g. `1,234,567` or `12,34,567`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Grouping {
+    primary: u8,
+    secondary: u8,
+    min_digits: u8,
+}
+
+impl Grouping {
+    /// Groups of three, `1,234,567`.
+    pub const THOUSANDS: Self = Self::new(3, 3, 1);
+
+    /// Groups of three, but only from five digits, `1234` and `12,345`.
+    pub const THOUSANDS_MIN_2: Self = Self::new(3, 3, 2);
+
+    /// The Indian (vedic) grouping, three then groups of two, `12,34,567`.
+    pub const VEDIC: Self = Self::new(3, 2, 1);
+
+    /// The Chinese myriad grouping, groups of four, `123,4567`.
+    pub const MYRIAD: Self = Self::new(4, 4, 1);
+
+    /// No grouping, `1234567`.
+    pub const NONE: Self = Self::new(0, 0, 1);
+
+    /// It creates a new grouping specification.
+    ///
+    /// Arguments:
+    ///
+    /// * `primary`: The size of the group closest to the decimal separator, `0` for none.
+    /// * `secondary`: The size of the other groups, `0` for the primary size.
+    /// * `min_digits`: The number of digits past the primary group needed before grouping
+    ///   starts, e.g. `2` to leave `1234` ungrouped.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Grouping struct.
+    pub const fn new(
+        primary: u8,
+        secondary: u8,
+        min_digits: u8,
+    ) -> Self {
+        Self {
+            primary,
+            secondary: if secondary == 0 { primary } else { secondary },
+            min_digits: if min_digits == 0 { 1 } else { min_digits },
+        }
+    }
+
+    /// It groups the digits of an integer.
+    ///
+    /// Arguments:
+    ///
+    /// * `digits`: The digits to group, without a sign.
+    /// * `separator`: The string put between groups.
+    ///
+    /// Returns:
+    ///
+    /// The grouped digits.
+    pub fn apply(
+        &self,
+        digits: &str,
+        separator: &str,
+    ) -> String {
+        let mut grouped = String::with_capacity(digits.len() * (separator.len() + 1));
+
+        self.write(digits, separator, &mut grouped)
+            .expect("writing to a String does not fail");
+
+        grouped
+    }
+
+    /// It writes the grouped digits of an integer, without allocating.
+    ///
+    /// Arguments:
+    ///
+    /// * `digits`: The ASCII digits to group, without a sign.
+    /// * `separator`: The string put between groups.
+    /// * `w`: The writer.
+    pub fn write<W: std::fmt::Write + ?Sized>(
+        &self,
+        digits: &str,
+        separator: &str,
+        w: &mut W,
+    ) -> std::fmt::Result {
+        let primary = self.primary as usize;
+        let secondary = self.secondary as usize;
+
+        if primary == 0 || digits.len() < primary + self.min_digits as usize {
+            return w.write_str(digits);
+        }
+
+        let (head, tail) = digits.split_at(digits.len() - primary);
+
+        let first = match head.len() % secondary {
+            0 => secondary,
+            n => n,
+        };
+
+        w.write_str(&head[..first])?;
+
+        for start in (first..head.len()).step_by(secondary) {
+            w.write_str(separator)?;
+            w.write_str(&head[start..start + secondary])?;
+        }
+
+        w.write_str(separator)?;
+        w.write_str(tail)
+    }
+}
+
+impl Default for Grouping {
+    fn default() -> Self {
+        Self::THOUSANDS
+    }
+}
+
+// GETTERS
+impl Grouping {
+    pub fn primary(&self) -> u8 {
+        self.primary
+    }
+
+    pub fn secondary(&self) -> u8 {
+        self.secondary
+    }
+
+    pub fn min_digits(&self) -> u8 {
+        self.min_digits
+    }
+}
@@ -1,24 +1,108 @@
 pub mod add;
+pub mod cmp;
 pub mod div;
 pub mod mul;
 pub mod sub;
+pub mod sum;
 
-use crate::Currency;
-use std::fmt::Display;
+use crate::{currency_math, Backend, Currency, Grouping};
+use std::fmt::{Alignment, Display, Write};
 
-impl Display for Currency {
+/// `{}` writes the rounded value, e.g. `-1234.50`, and `{:#}` the value formatted with the
+/// pattern, symbol and separators of the options, e.g. `-$1,234.50`.
+///
+/// The precision, e.g. `{:.4}`, overrides the decimal places of the options, `{:+}` writes the
+/// sign of positive values, and the width, fill and alignment pad the value, which is aligned
+/// to the right by default. `{:012}` pads the plain value with zeros after its sign.
+impl<B: Backend> Display for Currency<B> {
     fn fmt(
         &self,
         f: &mut std::fmt::Formatter<'_>,
     ) -> std::fmt::Result {
-        let precision = self.opts.precision();
+        let precision = f.precision().map(|p| p.min(u32::MAX as usize) as u32);
 
-        let increment = self.opts.increment();
+        let (rounded, scale) = self.rounded_minor(precision);
 
-        let rounded_value = Self::rounding(self.value, increment);
+        let alternate = f.alternate();
 
-        let currency: String = Self::round_dp_to_string(rounded_value, precision as usize);
+        let plus = f.sign_plus()
+            && if alternate {
+                !self.is_negative()
+            } else {
+                rounded >= 0
+            };
 
-        write!(f, "{currency}")
+        let sign = |w: &mut dyn Write| match (plus, rounded < 0 && !alternate) {
+            (true, _) => w.write_char('+'),
+            (_, true) => w.write_char('-'),
+            _ => Ok(()),
+        };
+
+        let digits = |w: &mut dyn Write| {
+            currency_math::write_unsigned_decimal(w, rounded, scale, Grouping::NONE, "", ".")
+        };
+
+        let render = |w: &mut dyn Write| {
+            sign(w)?;
+
+            if alternate {
+                self.write_pattern(w, rounded, scale)
+            } else {
+                digits(w)
+            }
+        };
+
+        let Some(width) = f.width() else {
+            return render(f);
+        };
+
+        let mut len = CharCount(0);
+
+        render(&mut len)?;
+
+        let pad = width.saturating_sub(len.0);
+
+        if f.sign_aware_zero_pad() && !alternate {
+            sign(f)?;
+
+            for _ in 0..pad {
+                f.write_char('0')?;
+            }
+
+            return digits(f);
+        }
+
+        let (before, after) = match f.align() {
+            Some(Alignment::Left) => (0, pad),
+            Some(Alignment::Center) => (pad / 2, pad - pad / 2),
+            _ => (pad, 0),
+        };
+
+        let fill = f.fill();
+
+        for _ in 0..before {
+            f.write_char(fill)?;
+        }
+
+        render(f)?;
+
+        for _ in 0..after {
+            f.write_char(fill)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A writer that only counts the characters written, to pad without allocating.
+struct CharCount(usize);
+
+impl Write for CharCount {
+    fn write_str(
+        &mut self,
+        s: &str,
+    ) -> std::fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
     }
 }
@@ -1,19 +1,58 @@
-use crate::{Currency, CurrencyErr};
+use crate::{Backend, Currency, CurrencyErr};
 use std::ops::{Add, AddAssign};
 
-impl Add for Currency {
-    type Output = Currency;
+/// Panics when both currencies have a code and they differ, see `Currency::try_add`.
+impl<B: Backend> Add for Currency<B> {
+    type Output = Currency<B>;
+
+    fn add(
+        mut self,
+        rhs: Self,
+    ) -> Self::Output {
+        self += &rhs;
+        self
+    }
+}
+
+/// Panics when both currencies have a code and they differ, see `Currency::try_add`.
+impl<B: Backend> Add<&Currency<B>> for Currency<B> {
+    type Output = Currency<B>;
+
+    fn add(
+        mut self,
+        rhs: &Currency<B>,
+    ) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+/// Panics when both currencies have a code and they differ, see `Currency::try_add`.
+impl<B: Backend> Add<Currency<B>> for &Currency<B> {
+    type Output = Currency<B>;
+
+    fn add(
+        self,
+        rhs: Currency<B>,
+    ) -> Self::Output {
+        self.clone() + &rhs
+    }
+}
+
+/// Panics when both currencies have a code and they differ, see `Currency::try_add`.
+impl<B: Backend> Add for &Currency<B> {
+    type Output = Currency<B>;
 
     fn add(
         self,
         rhs: Self,
     ) -> Self::Output {
-        self.add(rhs.value())
+        self.clone() + rhs
     }
 }
 
-impl Add<f64> for Currency {
-    type Output = Currency;
+impl<B: Backend> Add<f64> for Currency<B> {
+    type Output = Currency<B>;
 
     fn add(
         self,
@@ -23,8 +62,19 @@ impl Add<f64> for Currency {
     }
 }
 
-impl Add<&str> for Currency {
-    type Output = Result<Currency, CurrencyErr>;
+impl<B: Backend> Add<f64> for &Currency<B> {
+    type Output = Currency<B>;
+
+    fn add(
+        self,
+        rhs: f64,
+    ) -> Self::Output {
+        self.clone().add(rhs)
+    }
+}
+
+impl<B: Backend> Add<&str> for Currency<B> {
+    type Output = Result<Currency<B>, CurrencyErr>;
 
     fn add(
         self,
@@ -34,22 +84,36 @@ impl Add<&str> for Currency {
     }
 }
 
-/// Assign
+// Assign
 
-impl AddAssign for Currency {
+/// Panics when both currencies have a code and they differ, see `Currency::try_add`.
+impl<B: Backend> AddAssign for Currency<B> {
     fn add_assign(
         &mut self,
         rhs: Self,
     ) {
-        *self = self.clone().add(rhs.value())
+        *self += &rhs
+    }
+}
+
+/// Panics when both currencies have a code and they differ, see `Currency::try_add`.
+impl<B: Backend> AddAssign<&Currency<B>> for Currency<B> {
+    fn add_assign(
+        &mut self,
+        rhs: &Currency<B>,
+    ) {
+        self.check_code(rhs)
+            .expect("cannot add currencies with different codes");
+
+        self.add_exact_mut(rhs)
     }
 }
 
-impl AddAssign<f64> for Currency {
+impl<B: Backend> AddAssign<f64> for Currency<B> {
     fn add_assign(
         &mut self,
         rhs: f64,
     ) {
-        *self = self.clone().add(rhs)
+        self.add_mut(rhs)
     }
 }
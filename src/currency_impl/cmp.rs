@@ -0,0 +1,57 @@
+use crate::{Backend, Currency, Decimal};
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+impl<B: Backend> Currency<B> {
+    /// It returns the exact value of the currency as a decimal, for comparing and hashing.
+    fn exact(&self) -> Decimal {
+        Decimal::new(self.minor_units(), self.opts.precision())
+    }
+}
+
+/// Two currencies are equal when they have the same code, or both have none, and the same exact
+/// value, whatever their precision and formatting options. `1.5` and `1.500` are equal.
+impl<B: Backend> PartialEq for Currency<B> {
+    fn eq(
+        &self,
+        other: &Self,
+    ) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<B: Backend> Eq for Currency<B> {}
+
+impl<B: Backend> PartialOrd for Currency<B> {
+    fn partial_cmp(
+        &self,
+        other: &Self,
+    ) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Currencies are ordered by code first, no code coming first, then by exact value. Amounts of
+/// one currency are therefore ordered by value, and `min`, `max` and `clamp` only make sense
+/// between amounts of the same code.
+impl<B: Backend> Ord for Currency<B> {
+    fn cmp(
+        &self,
+        other: &Self,
+    ) -> Ordering {
+        self.opts
+            .code()
+            .cmp(&other.opts.code())
+            .then_with(|| self.exact().cmp(&other.exact()))
+    }
+}
+
+impl<B: Backend> Hash for Currency<B> {
+    fn hash<H: Hasher>(
+        &self,
+        state: &mut H,
+    ) {
+        self.opts.code().hash(state);
+        self.exact().hash(state);
+    }
+}
@@ -1,7 +1,7 @@
-use crate::Currency;
+use crate::{Backend, Currency};
 use std::ops::{Div, DivAssign};
 
-impl Div<f64> for Currency {
+impl<B: Backend> Div<f64> for Currency<B> {
     type Output = Self;
 
     fn div(
@@ -12,13 +12,24 @@ impl Div<f64> for Currency {
     }
 }
 
-/// Assign
+impl<B: Backend> Div<f64> for &Currency<B> {
+    type Output = Currency<B>;
 
-impl DivAssign<f64> for Currency {
+    fn div(
+        self,
+        rhs: f64,
+    ) -> Self::Output {
+        self.clone().divide(rhs)
+    }
+}
+
+// Assign
+
+impl<B: Backend> DivAssign<f64> for Currency<B> {
     fn div_assign(
         &mut self,
         rhs: f64,
     ) {
-        *self = self.clone().divide(rhs);
+        self.divide_mut(rhs);
     }
 }
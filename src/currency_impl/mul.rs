@@ -1,7 +1,7 @@
-use crate::Currency;
+use crate::{Backend, Currency};
 use std::ops::{Mul, MulAssign};
 
-impl Mul<f64> for Currency {
+impl<B: Backend> Mul<f64> for Currency<B> {
     type Output = Self;
 
     fn mul(
@@ -12,13 +12,24 @@ impl Mul<f64> for Currency {
     }
 }
 
-/// Assign
+impl<B: Backend> Mul<f64> for &Currency<B> {
+    type Output = Currency<B>;
 
-impl MulAssign<f64> for Currency {
+    fn mul(
+        self,
+        rhs: f64,
+    ) -> Self::Output {
+        self.clone().multiply(rhs)
+    }
+}
+
+// Assign
+
+impl<B: Backend> MulAssign<f64> for Currency<B> {
     fn mul_assign(
         &mut self,
         rhs: f64,
     ) {
-        *self = self.clone().multiply(rhs)
+        self.multiply_mut(rhs)
     }
 }
@@ -1,19 +1,58 @@
-use crate::Currency;
+use crate::{Backend, Currency};
 use std::ops::{Sub, SubAssign};
 
-impl Sub for Currency {
-    type Output = Currency;
+/// Panics when both currencies have a code and they differ, see `Currency::try_subtract`.
+impl<B: Backend> Sub for Currency<B> {
+    type Output = Currency<B>;
+
+    fn sub(
+        mut self,
+        rhs: Self,
+    ) -> Self::Output {
+        self -= &rhs;
+        self
+    }
+}
+
+/// Panics when both currencies have a code and they differ, see `Currency::try_subtract`.
+impl<B: Backend> Sub<&Currency<B>> for Currency<B> {
+    type Output = Currency<B>;
+
+    fn sub(
+        mut self,
+        rhs: &Currency<B>,
+    ) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+/// Panics when both currencies have a code and they differ, see `Currency::try_subtract`.
+impl<B: Backend> Sub<Currency<B>> for &Currency<B> {
+    type Output = Currency<B>;
+
+    fn sub(
+        self,
+        rhs: Currency<B>,
+    ) -> Self::Output {
+        self.clone() - &rhs
+    }
+}
+
+/// Panics when both currencies have a code and they differ, see `Currency::try_subtract`.
+impl<B: Backend> Sub for &Currency<B> {
+    type Output = Currency<B>;
 
     fn sub(
         self,
         rhs: Self,
     ) -> Self::Output {
-        self.subtract(rhs.value())
+        self.clone() - rhs
     }
 }
 
-impl Sub<f64> for Currency {
-    type Output = Currency;
+impl<B: Backend> Sub<f64> for Currency<B> {
+    type Output = Currency<B>;
 
     fn sub(
         self,
@@ -23,22 +62,47 @@ impl Sub<f64> for Currency {
     }
 }
 
-/// Assign
+impl<B: Backend> Sub<f64> for &Currency<B> {
+    type Output = Currency<B>;
+
+    fn sub(
+        self,
+        rhs: f64,
+    ) -> Self::Output {
+        self.clone().subtract(rhs)
+    }
+}
 
-impl SubAssign for Currency {
+// Assign
+
+/// Panics when both currencies have a code and they differ, see `Currency::try_subtract`.
+impl<B: Backend> SubAssign for Currency<B> {
     fn sub_assign(
         &mut self,
         rhs: Self,
     ) {
-        *self = self.clone().subtract(rhs.value());
+        *self -= &rhs;
+    }
+}
+
+/// Panics when both currencies have a code and they differ, see `Currency::try_subtract`.
+impl<B: Backend> SubAssign<&Currency<B>> for Currency<B> {
+    fn sub_assign(
+        &mut self,
+        rhs: &Currency<B>,
+    ) {
+        self.check_code(rhs)
+            .expect("cannot subtract currencies with different codes");
+
+        self.subtract_exact_mut(rhs);
     }
 }
 
-impl SubAssign<f64> for Currency {
+impl<B: Backend> SubAssign<f64> for Currency<B> {
     fn sub_assign(
         &mut self,
         rhs: f64,
     ) {
-        *self = self.clone().subtract(rhs);
+        self.subtract_mut(rhs);
     }
 }
@@ -0,0 +1,70 @@
+use crate::{Backend, Currency, CurrencyErr, CurrencyOpts};
+use std::borrow::Borrow;
+use std::iter::Sum;
+
+impl<B: Backend> Currency<B> {
+    /// It sums currencies, keeping the options of the first one, rejecting currencies of a
+    /// different code.
+    ///
+    /// Arguments:
+    ///
+    /// * `iter`: The currencies to sum, owned or borrowed.
+    ///
+    /// Returns:
+    ///
+    /// The sum, zero with the default options when there is nothing to sum,
+    /// `CurrencyErr::CurrencyMismatch` when two currencies have different codes, or
+    /// `CurrencyErr::Overflow` when the sum does not fit.
+    pub fn try_sum<C, I>(iter: I) -> Result<Self, CurrencyErr>
+    where
+        C: Borrow<Self>,
+        I: IntoIterator<Item = C>,
+    {
+        let mut iter = iter.into_iter();
+
+        let Some(first) = iter.next() else {
+            return Ok(Self::zero());
+        };
+
+        iter.try_fold(first.borrow().clone(), |sum, cur| {
+            sum.try_add_cur(cur.borrow())
+        })
+    }
+
+    fn zero() -> Self {
+        Self::new(B::default(), CurrencyOpts::new())
+    }
+
+    /// It adds the exact minor units of a currency to the sum, saturating like `+`.
+    fn add_to_sum(
+        mut self,
+        cur: &Self,
+    ) -> Self {
+        self.check_code(cur)
+            .expect("cannot add currencies with different codes");
+
+        self.add_exact_mut(cur);
+        self
+    }
+}
+
+/// The sum keeps the options of the first currency and adds the exact minor units of the others,
+/// whatever their `from_cents` option. It is zero with the default options when there is nothing
+/// to sum. Panics when two currencies have different codes, see
+/// `Currency::try_sum`.
+impl<B: Backend> Sum for Currency<B> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|sum, cur| sum.add_to_sum(&cur))
+            .unwrap_or_else(Self::zero)
+    }
+}
+
+/// Panics when two currencies have different codes, see `Currency::try_sum`.
+impl<'a, B: Backend> Sum<&'a Currency<B>> for Currency<B> {
+    fn sum<I: Iterator<Item = &'a Currency<B>>>(mut iter: I) -> Self {
+        match iter.next() {
+            Some(first) => iter.fold(first.clone(), Self::add_to_sum),
+            None => Self::zero(),
+        }
+    }
+}
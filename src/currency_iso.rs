@@ -0,0 +1,345 @@
+use crate::CurrencyOpts;
+
+/// An active ISO 4217 currency.
+///
+/// The list includes the fund codes, e.g. `CLF`, and the precious metals, units of account and
+/// special codes, e.g. `XAU`, `XDR` and `XXX`, whose minor units are "N.A." in the standard and
+/// are taken as 0 here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IsoCurrency {
+    code: &'static str,
+    numeric: u16,
+    name: &'static str,
+    minor_units: u32,
+    symbol: &'static str,
+    narrow_symbol: &'static str,
+}
+
+impl IsoCurrency {
+    const fn new(
+        code: &'static str,
+        numeric: u16,
+        name: &'static str,
+        minor_units: u32,
+        symbol: &'static str,
+        narrow_symbol: &'static str,
+    ) -> Self {
+        Self {
+            code,
+            numeric,
+            name,
+            minor_units,
+            symbol,
+            narrow_symbol,
+        }
+    }
+
+    /// It looks up a currency by its alphabetic code, ignoring case.
+    ///
+    /// Arguments:
+    ///
+    /// * `code`: The three letter code, e.g. `"JPY"`.
+    ///
+    /// Returns:
+    ///
+    /// The currency, or `None` when the code is not an active ISO 4217 code.
+    pub fn from_code(code: &str) -> Option<&'static Self> {
+        let code = code.trim().to_ascii_uppercase();
+
+        ISO_CURRENCIES
+            .binary_search_by(|c| c.code.cmp(code.as_str()))
+            .ok()
+            .map(|i| &ISO_CURRENCIES[i])
+    }
+
+    /// It looks up a currency by its numeric code.
+    ///
+    /// Arguments:
+    ///
+    /// * `numeric`: The numeric code, e.g. `392` for `JPY`.
+    ///
+    /// Returns:
+    ///
+    /// The currency, or `None` when the code is not an active ISO 4217 code.
+    pub fn from_numeric(numeric: u16) -> Option<&'static Self> {
+        ISO_CURRENCIES.iter().find(|c| c.numeric == numeric)
+    }
+
+    /// It returns every active ISO 4217 currency, ordered by alphabetic code.
+    pub fn all() -> &'static [Self] {
+        ISO_CURRENCIES
+    }
+
+    /// It returns currency options using the code, symbol and minor units of the currency.
+    pub fn opts(&self) -> CurrencyOpts {
+        CurrencyOpts::new()
+            .set_code(self.code)
+            .set_symbol(self.symbol)
+            .set_precision(self.minor_units)
+    }
+}
+
+// GETTERS
+impl IsoCurrency {
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    pub fn numeric(&self) -> u16 {
+        self.numeric
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn minor_units(&self) -> u32 {
+        self.minor_units
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        self.symbol
+    }
+
+    pub fn narrow_symbol(&self) -> &'static str {
+        self.narrow_symbol
+    }
+}
+
+/// Active ISO 4217 currencies, sorted by alphabetic code. Symbols follow the English CLDR data,
+/// falling back to the code where no symbol is in common use.
+static ISO_CURRENCIES: &[IsoCurrency] = &[
+    IsoCurrency::new("AED", 784, "UAE Dirham", 2, "AED", "AED"),
+    IsoCurrency::new("AFN", 971, "Afghani", 2, "AFN", "؋"),
+    IsoCurrency::new("ALL", 8, "Lek", 2, "ALL", "ALL"),
+    IsoCurrency::new("AMD", 51, "Armenian Dram", 2, "AMD", "֏"),
+    IsoCurrency::new("AOA", 973, "Kwanza", 2, "AOA", "Kz"),
+    IsoCurrency::new("ARS", 32, "Argentine Peso", 2, "ARS", "$"),
+    IsoCurrency::new("AUD", 36, "Australian Dollar", 2, "A$", "$"),
+    IsoCurrency::new("AWG", 533, "Aruban Florin", 2, "AWG", "AWG"),
+    IsoCurrency::new("AZN", 944, "Azerbaijan Manat", 2, "AZN", "₼"),
+    IsoCurrency::new("BAM", 977, "Convertible Mark", 2, "BAM", "KM"),
+    IsoCurrency::new("BBD", 52, "Barbados Dollar", 2, "BBD", "$"),
+    IsoCurrency::new("BDT", 50, "Taka", 2, "BDT", "৳"),
+    IsoCurrency::new("BHD", 48, "Bahraini Dinar", 3, "BHD", "BHD"),
+    IsoCurrency::new("BIF", 108, "Burundi Franc", 0, "BIF", "BIF"),
+    IsoCurrency::new("BMD", 60, "Bermudian Dollar", 2, "BMD", "$"),
+    IsoCurrency::new("BND", 96, "Brunei Dollar", 2, "BND", "$"),
+    IsoCurrency::new("BOB", 68, "Boliviano", 2, "BOB", "Bs"),
+    IsoCurrency::new("BOV", 984, "Mvdol", 2, "BOV", "BOV"),
+    IsoCurrency::new("BRL", 986, "Brazilian Real", 2, "R$", "R$"),
+    IsoCurrency::new("BSD", 44, "Bahamian Dollar", 2, "BSD", "$"),
+    IsoCurrency::new("BTN", 64, "Ngultrum", 2, "BTN", "BTN"),
+    IsoCurrency::new("BWP", 72, "Pula", 2, "BWP", "P"),
+    IsoCurrency::new("BYN", 933, "Belarusian Ruble", 2, "BYN", "BYN"),
+    IsoCurrency::new("BZD", 84, "Belize Dollar", 2, "BZD", "$"),
+    IsoCurrency::new("CAD", 124, "Canadian Dollar", 2, "CA$", "$"),
+    IsoCurrency::new("CDF", 976, "Congolese Franc", 2, "CDF", "CDF"),
+    IsoCurrency::new("CHE", 947, "WIR Euro", 2, "CHE", "CHE"),
+    IsoCurrency::new("CHF", 756, "Swiss Franc", 2, "CHF", "CHF"),
+    IsoCurrency::new("CHW", 948, "WIR Franc", 2, "CHW", "CHW"),
+    IsoCurrency::new("CLF", 990, "Unidad de Fomento", 4, "CLF", "CLF"),
+    IsoCurrency::new("CLP", 152, "Chilean Peso", 0, "CLP", "$"),
+    IsoCurrency::new("CNY", 156, "Yuan Renminbi", 2, "CN¥", "¥"),
+    IsoCurrency::new("COP", 170, "Colombian Peso", 2, "COP", "$"),
+    IsoCurrency::new("COU", 970, "Unidad de Valor Real", 2, "COU", "COU"),
+    IsoCurrency::new("CRC", 188, "Costa Rican Colon", 2, "CRC", "₡"),
+    IsoCurrency::new("CUP", 192, "Cuban Peso", 2, "CUP", "$"),
+    IsoCurrency::new("CVE", 132, "Cabo Verde Escudo", 2, "CVE", "CVE"),
+    IsoCurrency::new("CZK", 203, "Czech Koruna", 2, "CZK", "Kč"),
+    IsoCurrency::new("DJF", 262, "Djibouti Franc", 0, "DJF", "DJF"),
+    IsoCurrency::new("DKK", 208, "Danish Krone", 2, "DKK", "kr"),
+    IsoCurrency::new("DOP", 214, "Dominican Peso", 2, "DOP", "$"),
+    IsoCurrency::new("DZD", 12, "Algerian Dinar", 2, "DZD", "DZD"),
+    IsoCurrency::new("EGP", 818, "Egyptian Pound", 2, "EGP", "E£"),
+    IsoCurrency::new("ERN", 232, "Nakfa", 2, "ERN", "ERN"),
+    IsoCurrency::new("ETB", 230, "Ethiopian Birr", 2, "ETB", "ETB"),
+    IsoCurrency::new("EUR", 978, "Euro", 2, "€", "€"),
+    IsoCurrency::new("FJD", 242, "Fiji Dollar", 2, "FJD", "$"),
+    IsoCurrency::new("FKP", 238, "Falkland Islands Pound", 2, "FKP", "£"),
+    IsoCurrency::new("GBP", 826, "Pound Sterling", 2, "£", "£"),
+    IsoCurrency::new("GEL", 981, "Lari", 2, "GEL", "₾"),
+    IsoCurrency::new("GHS", 936, "Ghana Cedi", 2, "GHS", "GH₵"),
+    IsoCurrency::new("GIP", 292, "Gibraltar Pound", 2, "GIP", "£"),
+    IsoCurrency::new("GMD", 270, "Dalasi", 2, "GMD", "GMD"),
+    IsoCurrency::new("GNF", 324, "Guinean Franc", 0, "GNF", "FG"),
+    IsoCurrency::new("GTQ", 320, "Quetzal", 2, "GTQ", "Q"),
+    IsoCurrency::new("GYD", 328, "Guyana Dollar", 2, "GYD", "$"),
+    IsoCurrency::new("HKD", 344, "Hong Kong Dollar", 2, "HK$", "$"),
+    IsoCurrency::new("HNL", 340, "Lempira", 2, "HNL", "L"),
+    IsoCurrency::new("HTG", 332, "Gourde", 2, "HTG", "HTG"),
+    IsoCurrency::new("HUF", 348, "Forint", 2, "HUF", "Ft"),
+    IsoCurrency::new("IDR", 360, "Rupiah", 2, "IDR", "Rp"),
+    IsoCurrency::new("ILS", 376, "New Israeli Sheqel", 2, "₪", "₪"),
+    IsoCurrency::new("INR", 356, "Indian Rupee", 2, "₹", "₹"),
+    IsoCurrency::new("IQD", 368, "Iraqi Dinar", 3, "IQD", "IQD"),
+    IsoCurrency::new("IRR", 364, "Iranian Rial", 2, "IRR", "IRR"),
+    IsoCurrency::new("ISK", 352, "Iceland Krona", 0, "ISK", "kr"),
+    IsoCurrency::new("JMD", 388, "Jamaican Dollar", 2, "JMD", "$"),
+    IsoCurrency::new("JOD", 400, "Jordanian Dinar", 3, "JOD", "JOD"),
+    IsoCurrency::new("JPY", 392, "Yen", 0, "¥", "¥"),
+    IsoCurrency::new("KES", 404, "Kenyan Shilling", 2, "KES", "KES"),
+    IsoCurrency::new("KGS", 417, "Som", 2, "KGS", "⃀"),
+    IsoCurrency::new("KHR", 116, "Riel", 2, "KHR", "៛"),
+    IsoCurrency::new("KMF", 174, "Comorian Franc", 0, "KMF", "CF"),
+    IsoCurrency::new("KPW", 408, "North Korean Won", 2, "KPW", "₩"),
+    IsoCurrency::new("KRW", 410, "Won", 0, "₩", "₩"),
+    IsoCurrency::new("KWD", 414, "Kuwaiti Dinar", 3, "KWD", "KWD"),
+    IsoCurrency::new("KYD", 136, "Cayman Islands Dollar", 2, "KYD", "$"),
+    IsoCurrency::new("KZT", 398, "Tenge", 2, "KZT", "₸"),
+    IsoCurrency::new("LAK", 418, "Lao Kip", 2, "LAK", "₭"),
+    IsoCurrency::new("LBP", 422, "Lebanese Pound", 2, "LBP", "L£"),
+    IsoCurrency::new("LKR", 144, "Sri Lanka Rupee", 2, "LKR", "Rs"),
+    IsoCurrency::new("LRD", 430, "Liberian Dollar", 2, "LRD", "$"),
+    IsoCurrency::new("LSL", 426, "Loti", 2, "LSL", "LSL"),
+    IsoCurrency::new("LYD", 434, "Libyan Dinar", 3, "LYD", "LYD"),
+    IsoCurrency::new("MAD", 504, "Moroccan Dirham", 2, "MAD", "MAD"),
+    IsoCurrency::new("MDL", 498, "Moldovan Leu", 2, "MDL", "MDL"),
+    IsoCurrency::new("MGA", 969, "Malagasy Ariary", 2, "MGA", "Ar"),
+    IsoCurrency::new("MKD", 807, "Denar", 2, "MKD", "MKD"),
+    IsoCurrency::new("MMK", 104, "Kyat", 2, "MMK", "K"),
+    IsoCurrency::new("MNT", 496, "Tugrik", 2, "MNT", "₮"),
+    IsoCurrency::new("MOP", 446, "Pataca", 2, "MOP", "MOP"),
+    IsoCurrency::new("MRU", 929, "Ouguiya", 2, "MRU", "MRU"),
+    IsoCurrency::new("MUR", 480, "Mauritius Rupee", 2, "MUR", "Rs"),
+    IsoCurrency::new("MVR", 462, "Rufiyaa", 2, "MVR", "MVR"),
+    IsoCurrency::new("MWK", 454, "Malawi Kwacha", 2, "MWK", "MWK"),
+    IsoCurrency::new("MXN", 484, "Mexican Peso", 2, "MX$", "$"),
+    IsoCurrency::new(
+        "MXV",
+        979,
+        "Mexican Unidad de Inversion (UDI)",
+        2,
+        "MXV",
+        "MXV",
+    ),
+    IsoCurrency::new("MYR", 458, "Malaysian Ringgit", 2, "MYR", "RM"),
+    IsoCurrency::new("MZN", 943, "Mozambique Metical", 2, "MZN", "MZN"),
+    IsoCurrency::new("NAD", 516, "Namibia Dollar", 2, "NAD", "$"),
+    IsoCurrency::new("NGN", 566, "Naira", 2, "NGN", "₦"),
+    IsoCurrency::new("NIO", 558, "Cordoba Oro", 2, "NIO", "C$"),
+    IsoCurrency::new("NOK", 578, "Norwegian Krone", 2, "NOK", "kr"),
+    IsoCurrency::new("NPR", 524, "Nepalese Rupee", 2, "NPR", "Rs"),
+    IsoCurrency::new("NZD", 554, "New Zealand Dollar", 2, "NZ$", "$"),
+    IsoCurrency::new("OMR", 512, "Rial Omani", 3, "OMR", "OMR"),
+    IsoCurrency::new("PAB", 590, "Balboa", 2, "PAB", "PAB"),
+    IsoCurrency::new("PEN", 604, "Sol", 2, "PEN", "PEN"),
+    IsoCurrency::new("PGK", 598, "Kina", 2, "PGK", "PGK"),
+    IsoCurrency::new("PHP", 608, "Philippine Peso", 2, "₱", "₱"),
+    IsoCurrency::new("PKR", 586, "Pakistan Rupee", 2, "PKR", "Rs"),
+    IsoCurrency::new("PLN", 985, "Zloty", 2, "PLN", "zł"),
+    IsoCurrency::new("PYG", 600, "Guarani", 0, "PYG", "₲"),
+    IsoCurrency::new("QAR", 634, "Qatari Rial", 2, "QAR", "QAR"),
+    IsoCurrency::new("RON", 946, "Romanian Leu", 2, "RON", "lei"),
+    IsoCurrency::new("RSD", 941, "Serbian Dinar", 2, "RSD", "RSD"),
+    IsoCurrency::new("RUB", 643, "Russian Ruble", 2, "RUB", "₽"),
+    IsoCurrency::new("RWF", 646, "Rwanda Franc", 0, "RWF", "RF"),
+    IsoCurrency::new("SAR", 682, "Saudi Riyal", 2, "SAR", "SAR"),
+    IsoCurrency::new("SBD", 90, "Solomon Islands Dollar", 2, "SBD", "$"),
+    IsoCurrency::new("SCR", 690, "Seychelles Rupee", 2, "SCR", "SCR"),
+    IsoCurrency::new("SDG", 938, "Sudanese Pound", 2, "SDG", "SDG"),
+    IsoCurrency::new("SEK", 752, "Swedish Krona", 2, "SEK", "kr"),
+    IsoCurrency::new("SGD", 702, "Singapore Dollar", 2, "SGD", "$"),
+    IsoCurrency::new("SHP", 654, "Saint Helena Pound", 2, "SHP", "£"),
+    IsoCurrency::new("SLE", 925, "Leone", 2, "SLE", "SLE"),
+    IsoCurrency::new("SOS", 706, "Somali Shilling", 2, "SOS", "SOS"),
+    IsoCurrency::new("SRD", 968, "Surinam Dollar", 2, "SRD", "$"),
+    IsoCurrency::new("SSP", 728, "South Sudanese Pound", 2, "SSP", "£"),
+    IsoCurrency::new("STN", 930, "Dobra", 2, "STN", "Db"),
+    IsoCurrency::new("SVC", 222, "El Salvador Colon", 2, "SVC", "SVC"),
+    IsoCurrency::new("SYP", 760, "Syrian Pound", 2, "SYP", "£"),
+    IsoCurrency::new("SZL", 748, "Lilangeni", 2, "SZL", "SZL"),
+    IsoCurrency::new("THB", 764, "Baht", 2, "THB", "฿"),
+    IsoCurrency::new("TJS", 972, "Somoni", 2, "TJS", "TJS"),
+    IsoCurrency::new("TMT", 934, "Turkmenistan New Manat", 2, "TMT", "TMT"),
+    IsoCurrency::new("TND", 788, "Tunisian Dinar", 3, "TND", "TND"),
+    IsoCurrency::new("TOP", 776, "Pa’anga", 2, "TOP", "T$"),
+    IsoCurrency::new("TRY", 949, "Turkish Lira", 2, "TRY", "₺"),
+    IsoCurrency::new("TTD", 780, "Trinidad and Tobago Dollar", 2, "TTD", "$"),
+    IsoCurrency::new("TWD", 901, "New Taiwan Dollar", 2, "NT$", "$"),
+    IsoCurrency::new("TZS", 834, "Tanzanian Shilling", 2, "TZS", "TZS"),
+    IsoCurrency::new("UAH", 980, "Hryvnia", 2, "UAH", "₴"),
+    IsoCurrency::new("UGX", 800, "Uganda Shilling", 0, "UGX", "UGX"),
+    IsoCurrency::new("USD", 840, "US Dollar", 2, "$", "$"),
+    IsoCurrency::new("USN", 997, "US Dollar (Next day)", 2, "USN", "USN"),
+    IsoCurrency::new(
+        "UYI",
+        940,
+        "Uruguay Peso en Unidades Indexadas (UI)",
+        0,
+        "UYI",
+        "UYI",
+    ),
+    IsoCurrency::new("UYU", 858, "Peso Uruguayo", 2, "UYU", "$"),
+    IsoCurrency::new("UYW", 927, "Unidad Previsional", 4, "UYW", "UYW"),
+    IsoCurrency::new("UZS", 860, "Uzbekistan Sum", 2, "UZS", "UZS"),
+    IsoCurrency::new("VED", 926, "Bolívar Soberano", 2, "VED", "VED"),
+    IsoCurrency::new("VES", 928, "Bolívar Soberano", 2, "VES", "VES"),
+    IsoCurrency::new("VND", 704, "Dong", 0, "₫", "₫"),
+    IsoCurrency::new("VUV", 548, "Vatu", 0, "VUV", "VUV"),
+    IsoCurrency::new("WST", 882, "Tala", 2, "WST", "WST"),
+    IsoCurrency::new("XAF", 950, "CFA Franc BEAC", 0, "FCFA", "FCFA"),
+    IsoCurrency::new("XAG", 961, "Silver", 0, "XAG", "XAG"),
+    IsoCurrency::new("XAU", 959, "Gold", 0, "XAU", "XAU"),
+    IsoCurrency::new(
+        "XBA",
+        955,
+        "Bond Markets Unit European Composite Unit (EURCO)",
+        0,
+        "XBA",
+        "XBA",
+    ),
+    IsoCurrency::new(
+        "XBB",
+        956,
+        "Bond Markets Unit European Monetary Unit (E.M.U.-6)",
+        0,
+        "XBB",
+        "XBB",
+    ),
+    IsoCurrency::new(
+        "XBC",
+        957,
+        "Bond Markets Unit European Unit of Account 9 (E.U.A.-9)",
+        0,
+        "XBC",
+        "XBC",
+    ),
+    IsoCurrency::new(
+        "XBD",
+        958,
+        "Bond Markets Unit European Unit of Account 17 (E.U.A.-17)",
+        0,
+        "XBD",
+        "XBD",
+    ),
+    IsoCurrency::new("XCD", 951, "East Caribbean Dollar", 2, "EC$", "$"),
+    IsoCurrency::new("XCG", 532, "Caribbean Guilder", 2, "XCG", "Cg."),
+    IsoCurrency::new("XDR", 960, "SDR (Special Drawing Right)", 0, "XDR", "XDR"),
+    IsoCurrency::new("XOF", 952, "CFA Franc BCEAO", 0, "F CFA", "F CFA"),
+    IsoCurrency::new("XPD", 964, "Palladium", 0, "XPD", "XPD"),
+    IsoCurrency::new("XPF", 953, "CFP Franc", 0, "CFPF", "CFPF"),
+    IsoCurrency::new("XPT", 962, "Platinum", 0, "XPT", "XPT"),
+    IsoCurrency::new("XSU", 994, "Sucre", 0, "XSU", "XSU"),
+    IsoCurrency::new(
+        "XTS",
+        963,
+        "Codes specifically reserved for testing purposes",
+        0,
+        "XTS",
+        "XTS",
+    ),
+    IsoCurrency::new("XUA", 965, "ADB Unit of Account", 0, "XUA", "XUA"),
+    IsoCurrency::new(
+        "XXX",
+        999,
+        "The codes assigned for transactions where no currency is involved",
+        0,
+        "XXX",
+        "XXX",
+    ),
+    IsoCurrency::new("YER", 886, "Yemeni Rial", 2, "YER", "YER"),
+    IsoCurrency::new("ZAR", 710, "Rand", 2, "ZAR", "R"),
+    IsoCurrency::new("ZMW", 967, "Zambian Kwacha", 2, "ZMW", "ZK"),
+    IsoCurrency::new("ZWG", 924, "Zimbabwe Gold", 2, "ZWG", "ZWG"),
+];
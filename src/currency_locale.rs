@@ -0,0 +1,175 @@
+use crate::Grouping;
+
+/// Number formatting conventions of a locale, following the CLDR currency format data.
+///
+/// Patterns use `¤` for the currency symbol, `#` for the number and `-` for the minus sign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Locale {
+    tag: &'static str,
+    currency: &'static str,
+    decimal: &'static str,
+    group: &'static str,
+    minus_sign: &'static str,
+    grouping: Grouping,
+    pattern: &'static str,
+    negative_pattern: &'static str,
+}
+
+impl Locale {
+    const fn new(
+        tag: &'static str,
+        currency: &'static str,
+        decimal: &'static str,
+        group: &'static str,
+        minus_sign: &'static str,
+        grouping: Grouping,
+        patterns: (&'static str, &'static str),
+    ) -> Self {
+        Self {
+            tag,
+            currency,
+            decimal,
+            group,
+            minus_sign,
+            grouping,
+            pattern: patterns.0,
+            negative_pattern: patterns.1,
+        }
+    }
+
+    /// It looks up a locale by its BCP 47 tag, ignoring case and accepting `_` for `-`. A tag
+    /// that is not bundled falls back to its language, so `de-LI` formats like `de-DE`.
+    ///
+    /// Arguments:
+    ///
+    /// * `tag`: The locale tag, e.g. `"de-CH"`.
+    ///
+    /// Returns:
+    ///
+    /// The locale, or `None` when neither the tag nor its language is bundled.
+    pub fn from_tag(tag: &str) -> Option<&'static Self> {
+        let tag = tag.trim().replace('_', "-");
+
+        let language = tag.split('-').next().unwrap_or_default();
+
+        LOCALES
+            .iter()
+            .find(|l| l.tag.eq_ignore_ascii_case(&tag))
+            .or_else(|| {
+                LOCALES
+                    .iter()
+                    .find(|l| l.language().eq_ignore_ascii_case(language))
+            })
+    }
+
+    /// It returns every bundled locale, the default one of each language first.
+    pub fn all() -> &'static [Self] {
+        LOCALES
+    }
+
+    /// It returns the language subtag of the locale, e.g. `de` for `de-CH`.
+    pub fn language(&self) -> &'static str {
+        self.tag.split('-').next().unwrap_or(self.tag)
+    }
+}
+
+// GETTERS
+impl Locale {
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    /// The code of the currency of the locale, formatted with its narrow symbol.
+    pub fn currency(&self) -> &'static str {
+        self.currency
+    }
+
+    pub fn decimal(&self) -> &'static str {
+        self.decimal
+    }
+
+    pub fn group(&self) -> &'static str {
+        self.group
+    }
+
+    pub fn minus_sign(&self) -> &'static str {
+        self.minus_sign
+    }
+
+    pub fn grouping(&self) -> Grouping {
+        self.grouping
+    }
+
+    pub fn pattern(&self) -> &'static str {
+        self.pattern
+    }
+
+    pub fn negative_pattern(&self) -> &'static str {
+        self.negative_pattern
+    }
+}
+
+/// No-break space.
+const NBSP: &str = "\u{a0}";
+/// Narrow no-break space.
+const NNBSP: &str = "\u{202f}";
+/// Right single quotation mark, the Swiss group separator.
+const APOS: &str = "\u{2019}";
+/// Minus sign.
+const MINUS: &str = "\u{2212}";
+
+const GROUP_3: Grouping = Grouping::THOUSANDS;
+const GROUP_3_MIN_2: Grouping = Grouping::THOUSANDS_MIN_2;
+const GROUP_INDIAN: Grouping = Grouping::VEDIC;
+
+/// `¤1.00` and `-¤1.00`.
+const PREFIX: (&str, &str) = ("¤#", "-¤#");
+/// `¤ 1.00` and `-¤ 1.00`.
+const PREFIX_SPACED: (&str, &str) = ("¤\u{a0}#", "-¤\u{a0}#");
+/// `¤ 1.00` and `¤-1.00`.
+const PREFIX_SWISS: (&str, &str) = ("¤\u{a0}#", "¤-#");
+/// `¤ 1.00` and `¤ -1.00`.
+const PREFIX_DUTCH: (&str, &str) = ("¤\u{a0}#", "¤\u{a0}-#");
+/// `1.00 ¤` and `-1.00 ¤`.
+const SUFFIX: (&str, &str) = ("#\u{a0}¤", "-#\u{a0}¤");
+
+/// Bundled locales, following the CLDR number symbols and standard currency formats. The first
+/// locale of each language is the one the language falls back to.
+static LOCALES: &[Locale] = &[
+    Locale::new("en-US", "USD", ".", ",", "-", GROUP_3, PREFIX),
+    Locale::new("en-AU", "AUD", ".", ",", "-", GROUP_3, PREFIX),
+    Locale::new("en-CA", "CAD", ".", ",", "-", GROUP_3, PREFIX),
+    Locale::new("en-GB", "GBP", ".", ",", "-", GROUP_3, PREFIX),
+    Locale::new("en-IE", "EUR", ".", ",", "-", GROUP_3, PREFIX),
+    Locale::new("en-IN", "INR", ".", ",", "-", GROUP_INDIAN, PREFIX),
+    Locale::new("en-NZ", "NZD", ".", ",", "-", GROUP_3, PREFIX),
+    Locale::new("en-SG", "SGD", ".", ",", "-", GROUP_3, PREFIX),
+    Locale::new("de-DE", "EUR", ",", ".", "-", GROUP_3, SUFFIX),
+    Locale::new("de-AT", "EUR", ",", NBSP, "-", GROUP_3, PREFIX_SPACED),
+    Locale::new("de-CH", "CHF", ".", APOS, "-", GROUP_3, PREFIX_SWISS),
+    Locale::new("fr-FR", "EUR", ",", NNBSP, "-", GROUP_3, SUFFIX),
+    Locale::new("fr-BE", "EUR", ",", NNBSP, "-", GROUP_3, SUFFIX),
+    Locale::new("fr-CA", "CAD", ",", NBSP, "-", GROUP_3, SUFFIX),
+    Locale::new("fr-CH", "CHF", ",", NNBSP, "-", GROUP_3, SUFFIX),
+    Locale::new("it-IT", "EUR", ",", ".", "-", GROUP_3, SUFFIX),
+    Locale::new("it-CH", "CHF", ".", APOS, "-", GROUP_3, PREFIX_SWISS),
+    Locale::new("es-ES", "EUR", ",", ".", "-", GROUP_3_MIN_2, SUFFIX),
+    Locale::new("es-MX", "MXN", ".", ",", "-", GROUP_3, PREFIX),
+    Locale::new("pt-BR", "BRL", ",", ".", "-", GROUP_3, PREFIX_SPACED),
+    Locale::new("pt-PT", "EUR", ",", NBSP, "-", GROUP_3_MIN_2, SUFFIX),
+    Locale::new("nl-NL", "EUR", ",", ".", "-", GROUP_3, PREFIX_DUTCH),
+    Locale::new("nl-BE", "EUR", ",", ".", "-", GROUP_3, PREFIX_DUTCH),
+    Locale::new("da-DK", "DKK", ",", ".", "-", GROUP_3, SUFFIX),
+    Locale::new("sv-SE", "SEK", ",", NBSP, MINUS, GROUP_3, SUFFIX),
+    Locale::new("nb-NO", "NOK", ",", NBSP, MINUS, GROUP_3, SUFFIX),
+    Locale::new("fi-FI", "EUR", ",", NBSP, MINUS, GROUP_3, SUFFIX),
+    Locale::new("pl-PL", "PLN", ",", NBSP, "-", GROUP_3_MIN_2, SUFFIX),
+    Locale::new("cs-CZ", "CZK", ",", NBSP, "-", GROUP_3, SUFFIX),
+    Locale::new("ru-RU", "RUB", ",", NBSP, "-", GROUP_3, SUFFIX),
+    Locale::new("tr-TR", "TRY", ",", ".", "-", GROUP_3, PREFIX),
+    Locale::new("hi-IN", "INR", ".", ",", "-", GROUP_INDIAN, PREFIX),
+    Locale::new("ja-JP", "JPY", ".", ",", "-", GROUP_3, PREFIX),
+    Locale::new("ko-KR", "KRW", ".", ",", "-", GROUP_3, PREFIX),
+    Locale::new("zh-CN", "CNY", ".", ",", "-", GROUP_3, PREFIX),
+    Locale::new("zh-TW", "TWD", ".", ",", "-", GROUP_3, PREFIX),
+];
@@ -0,0 +1,1081 @@
+use crate::{CurrencyErr, Grouping, RoundingMode};
+use std::cmp::Ordering;
+
+/// The reason an exact decimal could not be produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DecimalErr {
+    Invalid,
+    Overflow { negative: bool },
+}
+
+/// `pow10` returns 10 raised to the power of p as an i128.
+///
+/// Arguments:
+///
+/// * `p`: The power to raise 10 to.
+///
+/// Returns:
+///
+/// `None` when the result does not fit in an i128.
+pub(crate) fn pow10(p: u32) -> Option<i128> {
+    10_i128.checked_pow(p)
+}
+
+/// It divides two integers, rounding the quotient with the given mode.
+///
+/// Arguments:
+///
+/// * `n`: The numerator.
+/// * `d`: The denominator, must not be zero.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// The rounded quotient.
+pub(crate) fn div_round(
+    n: i128,
+    d: i128,
+    mode: RoundingMode,
+) -> i128 {
+    let q = n / d;
+    let r = (n % d).unsigned_abs();
+    let d_abs = d.unsigned_abs();
+
+    let negative = (n < 0) != (d < 0);
+
+    if mode.round_away(q % 2, negative, r.cmp(&(d_abs - r)), r != 0) {
+        if negative {
+            q - 1
+        } else {
+            q + 1
+        }
+    } else {
+        q
+    }
+}
+
+/// It moves an exact decimal from one scale to another, rounding with the given mode when digits
+/// are dropped.
+///
+/// Arguments:
+///
+/// * `mantissa`: The digits of the decimal.
+/// * `from`: The number of decimal places of `mantissa`.
+/// * `to`: The number of decimal places wanted.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// `None` when the result does not fit in an i128.
+pub(crate) fn rescale(
+    mantissa: i128,
+    from: u32,
+    to: u32,
+    mode: RoundingMode,
+) -> Option<i128> {
+    if to >= from {
+        mantissa.checked_mul(pow10(to - from)?)
+    } else {
+        match pow10(from - to) {
+            Some(p) => Some(div_round(mantissa, p, mode)),
+            None => {
+                let away = mode.round_away(0, mantissa < 0, Ordering::Less, mantissa != 0);
+
+                Some(if !away {
+                    0
+                } else if mantissa < 0 {
+                    -1
+                } else {
+                    1
+                })
+            }
+        }
+    }
+}
+
+/// It parses a plain decimal string (`-123.456`) into an integer scaled to the given number of
+/// decimal places. Digits past `scale` are rounded with the given mode.
+///
+/// Arguments:
+///
+/// * `value`: The string to parse, made of an optional `-`, digits and an optional `.`.
+/// * `scale`: The number of decimal places of the result.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// The scaled integer, or the reason it could not be produced.
+pub(crate) fn parse_decimal(
+    value: &str,
+    scale: u32,
+    mode: RoundingMode,
+) -> Result<i128, DecimalErr> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value),
+    };
+
+    let (int_part, frac_part) = match digits.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, frac_part),
+        None => (digits, ""),
+    };
+
+    let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+
+    if int_part.len() + frac_part.len() == 0 || !all_digits(int_part) || !all_digits(frac_part) {
+        return Err(DecimalErr::Invalid);
+    }
+
+    let overflow = DecimalErr::Overflow { negative };
+
+    let push = |acc: i128, digit: u8| -> Result<i128, DecimalErr> {
+        acc.checked_mul(10)
+            .and_then(|v| v.checked_add((digit - b'0') as i128))
+            .ok_or(overflow)
+    };
+
+    let mut acc: i128 = 0;
+
+    for digit in int_part.bytes() {
+        acc = push(acc, digit)?;
+    }
+
+    let mut frac = frac_part.bytes();
+
+    for _ in 0..scale {
+        acc = push(acc, frac.next().unwrap_or(b'0'))?;
+    }
+
+    let first_dropped = frac.next().unwrap_or(b'0');
+    let rest_inexact = frac.any(|digit| digit != b'0');
+
+    let half = match first_dropped.cmp(&b'5') {
+        Ordering::Equal if rest_inexact => Ordering::Greater,
+        ordering => ordering,
+    };
+
+    if mode.round_away(acc, negative, half, first_dropped != b'0' || rest_inexact) {
+        acc = acc.checked_add(1).ok_or(overflow)?;
+    }
+
+    Ok(if negative { -acc } else { acc })
+}
+
+/// It converts a float into the exact decimal of its shortest round-trip representation, so that
+/// `0.1` becomes `1` with a scale of `1` rather than its binary approximation.
+///
+/// Arguments:
+///
+/// * `value`: The float to convert.
+///
+/// Returns:
+///
+/// A tuple of the mantissa and its number of decimal places, or `None` when the value is not
+/// finite or does not fit in an i128.
+pub(crate) fn decimal_from_f64(value: f64) -> Option<(i128, u32)> {
+    let (m, e) = sci_from_f64(value)?;
+
+    if e >= 0 {
+        Some((m.checked_mul(pow10(e as u32)?)?, 0))
+    } else {
+        Some((m, e.unsigned_abs()))
+    }
+}
+
+/// It converts a float into the digits and exponent of its shortest round-trip representation,
+/// without allocating, so that `1.5e-300` becomes `15` and `-301`.
+///
+/// Arguments:
+///
+/// * `value`: The float to convert.
+///
+/// Returns:
+///
+/// A tuple of the mantissa and the power of ten it is multiplied by, or `None` when the value is
+/// not finite.
+pub(crate) fn sci_from_f64(value: f64) -> Option<(i128, i32)> {
+    use std::fmt::Write;
+
+    if !value.is_finite() {
+        return None;
+    }
+
+    let mut buf = StackStr::default();
+    write!(buf, "{value:e}").ok()?;
+
+    let (digits, exp) = buf.as_str().split_once('e')?;
+    let (negative, digits) = match digits.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, digits),
+    };
+
+    let frac = digits.split_once('.').map_or(0, |(_, frac)| frac.len()) as i32;
+
+    let m = digits
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .fold(0_i128, |m, b| m * 10 + (b - b'0') as i128);
+
+    Some((
+        if negative { -m } else { m },
+        exp.parse::<i32>().ok()? - frac,
+    ))
+}
+
+/// A small string on the stack, long enough for the scientific notation of any float.
+#[derive(Default)]
+struct StackStr {
+    buf: [u8; 32],
+    len: usize,
+}
+
+impl StackStr {
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+}
+
+impl std::fmt::Write for StackStr {
+    fn write_str(
+        &mut self,
+        s: &str,
+    ) -> std::fmt::Result {
+        let end = self.len + s.len();
+
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(std::fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// It converts a float into an integer scaled to the given number of decimal places, using the
+/// exact decimal of the float where possible.
+///
+/// Arguments:
+///
+/// * `value`: The float to convert.
+/// * `scale`: The number of decimal places of the result.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// The scaled integer, saturating at the bounds of an i128.
+pub(crate) fn scale_f64(
+    value: f64,
+    scale: u32,
+    mode: RoundingMode,
+) -> i128 {
+    match checked_scale_f64(value, scale, mode) {
+        Ok(v) => v,
+        Err(_) => mode.round_f64(value * 10_f64.powi(scale as i32)) as i128,
+    }
+}
+
+/// It converts a float into an integer scaled to the given number of decimal places, using the
+/// exact decimal of the float.
+///
+/// Arguments:
+///
+/// * `value`: The float to convert.
+/// * `scale`: The number of decimal places of the result.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// The scaled integer, or why it could not be computed exactly.
+pub(crate) fn checked_scale_f64(
+    value: f64,
+    scale: u32,
+    mode: RoundingMode,
+) -> Result<i128, CurrencyErr> {
+    if !value.is_finite() {
+        return Err(CurrencyErr::NonFiniteNumber);
+    }
+
+    decimal_from_f64(value)
+        .and_then(|(m, s)| rescale(m, s, scale, mode))
+        .ok_or(CurrencyErr::Overflow)
+}
+
+/// It computes `value * m / d` without overflowing in between, rounding the quotient with the
+/// given mode.
+///
+/// Arguments:
+///
+/// * `value`: The integer to scale.
+/// * `m`: The multiplier.
+/// * `d`: The divisor, must not be zero.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// `None` when the result does not fit in an i128.
+pub(crate) fn mul_div_round(
+    value: i128,
+    m: i128,
+    d: i128,
+    mode: RoundingMode,
+) -> Option<i128> {
+    let negative = ((value < 0) ^ (m < 0) ^ (d < 0)) && value != 0 && m != 0;
+
+    let (v, m, d) = (value.unsigned_abs(), m.unsigned_abs(), d.unsigned_abs());
+
+    let high = (v / d).checked_mul(m)?;
+    let Some(low) = (v % d).checked_mul(m) else {
+        return round_wide(mul_wide(v, m), d, false, negative, mode);
+    };
+
+    let mut magnitude = high.checked_add(low / d)?;
+    let r = low % d;
+
+    if mode.round_away((magnitude % 2) as i128, negative, r.cmp(&(d - r)), r != 0) {
+        magnitude = magnitude.checked_add(1)?;
+    }
+
+    if negative {
+        0_i128.checked_sub_unsigned(magnitude)
+    } else {
+        i128::try_from(magnitude).ok()
+    }
+}
+
+/// It computes `value * m / 10^scale` for any scale, rounding the quotient once with the given
+/// mode, so that a product too small to show rounds to zero instead of failing.
+///
+/// Arguments:
+///
+/// * `value`: The integer to scale.
+/// * `m`: The multiplier.
+/// * `scale`: The power of ten to divide by.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// `None` when the result does not fit in an i128.
+pub(crate) fn mul_div_pow10(
+    value: i128,
+    m: i128,
+    scale: u32,
+    mode: RoundingMode,
+) -> Option<i128> {
+    mul_add_div_pow10(value, 0, m, scale, mode)
+}
+
+/// It computes `value * (base + m / 10^scale)` for any scale, rounding once with the given mode,
+/// so that a fraction too small to show rounds away instead of failing.
+///
+/// Arguments:
+///
+/// * `value`: The integer to scale.
+/// * `base`: The whole part of the factor.
+/// * `m`: The digits of the fractional part of the factor.
+/// * `scale`: The number of decimal places of `m`.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// `None` when the result does not fit in an i128.
+pub(crate) fn mul_add_div_pow10(
+    value: i128,
+    base: i128,
+    m: i128,
+    scale: u32,
+    mode: RoundingMode,
+) -> Option<i128> {
+    if let Some(d) = pow10(scale) {
+        if let Some(factor) = base.checked_mul(d).and_then(|b| b.checked_add(m)) {
+            return mul_div_round(value, factor, d, mode);
+        }
+    }
+
+    let offset = value.checked_mul(base)?;
+    let negative = ((value < 0) ^ (m < 0)) && value != 0 && m != 0;
+
+    let mut n = mul_wide(value.unsigned_abs(), m.unsigned_abs());
+    let mut scale = scale;
+    let mut sticky = false;
+
+    // 10^38 is the largest power of ten that fits, divide by it until the rest fits too
+    while scale > 38 && n != (0, 0) {
+        let (q, r) = div_wide(n, 10_u128.pow(38));
+
+        sticky |= r != 0;
+        n = q;
+        scale -= 38;
+    }
+
+    let (q, half, inexact) = div_half(n, 10_u128.pow(scale.min(38)), sticky);
+
+    add_rounded(offset, q, negative, half, inexact, mode)
+}
+
+/// It computes `value / (1 - m / 10^scale)` for any scale, rounding the quotient once with the
+/// given mode.
+///
+/// Arguments:
+///
+/// * `value`: The integer to divide.
+/// * `m`: The digits of the fraction to take from one, `m / 10^scale` must not be one.
+/// * `scale`: The number of decimal places of `m`.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// `None` when the result does not fit in an i128.
+pub(crate) fn div_one_minus_pow10(
+    value: i128,
+    m: i128,
+    scale: u32,
+    mode: RoundingMode,
+) -> Option<i128> {
+    if let Some(one) = pow10(scale) {
+        return mul_div_round(value, one, one.checked_sub(m)?, mode);
+    }
+
+    // the fraction is below one, so the quotient is `value + value * m / (10^scale - m)`
+    let negative = ((value < 0) ^ (m < 0)) && value != 0 && m != 0;
+
+    let p = mul_wide(value.unsigned_abs(), m.unsigned_abs());
+
+    // past 10^76 the divisor is more than twice any product of two i128
+    let (q, half, inexact) = if scale > 76 {
+        ((0, 0), Ordering::Less, p != (0, 0))
+    } else {
+        let one = mul_wide(10_u128.pow(38), 10_u128.pow(scale - 38));
+        let d = if m < 0 {
+            add_u256(one, (0, m.unsigned_abs()))
+        } else {
+            sub_u256(one, (0, m.unsigned_abs()))
+        };
+
+        let (q, r) = div_u256(p, d);
+
+        (q, r.cmp(&sub_u256(d, r)), r != (0, 0))
+    };
+
+    add_rounded(value, q, negative, half, inexact, mode)
+}
+
+/// It multiplies two unsigned integers into a 256-bit product.
+///
+/// Returns:
+///
+/// The high and low halves of the product.
+pub(crate) fn mul_wide(
+    a: u128,
+    b: u128,
+) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+
+    let (a1, a0) = (a >> 64, a & MASK);
+    let (b1, b0) = (b >> 64, b & MASK);
+
+    let (p00, p01, p10, p11) = (a0 * b0, a0 * b1, a1 * b0, a1 * b1);
+
+    let mid = (p00 >> 64) + (p01 & MASK) + (p10 & MASK);
+
+    (
+        p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64),
+        (p00 & MASK) | (mid << 64),
+    )
+}
+
+/// It divides a 256-bit integer by a 128-bit one.
+///
+/// Arguments:
+///
+/// * `n`: The high and low halves of the dividend.
+/// * `d`: The divisor, must not be zero.
+///
+/// Returns:
+///
+/// The quotient as high and low halves, and the remainder.
+fn div_wide(
+    n: (u128, u128),
+    d: u128,
+) -> ((u128, u128), u128) {
+    let (high, mut r) = (n.0 / d, n.0 % d);
+    let mut low = 0_u128;
+
+    for i in (0..128).rev() {
+        let carry = r >> 127;
+
+        r = (r << 1) | ((n.1 >> i) & 1);
+        low <<= 1;
+
+        if carry == 1 || r >= d {
+            r = r.wrapping_sub(d);
+            low |= 1;
+        }
+    }
+
+    ((high, low), r)
+}
+
+/// It adds two 256-bit integers given as high and low halves.
+fn add_u256(
+    a: (u128, u128),
+    b: (u128, u128),
+) -> (u128, u128) {
+    let (low, carry) = a.1.overflowing_add(b.1);
+
+    (a.0 + b.0 + carry as u128, low)
+}
+
+/// It subtracts a 256-bit integer from a larger one, both given as high and low halves.
+fn sub_u256(
+    a: (u128, u128),
+    b: (u128, u128),
+) -> (u128, u128) {
+    let (low, borrow) = a.1.overflowing_sub(b.1);
+
+    (a.0 - b.0 - borrow as u128, low)
+}
+
+/// It divides two 256-bit integers.
+///
+/// Arguments:
+///
+/// * `n`: The high and low halves of the dividend.
+/// * `d`: The high and low halves of the divisor, must not be zero and below 2<sup>255</sup>.
+///
+/// Returns:
+///
+/// The quotient and the remainder.
+fn div_u256(
+    n: (u128, u128),
+    d: (u128, u128),
+) -> ((u128, u128), (u128, u128)) {
+    let mut q = (0_u128, 0_u128);
+    let mut r = (0_u128, 0_u128);
+
+    for i in (0..256).rev() {
+        let bit = if i >= 128 {
+            (n.0 >> (i - 128)) & 1
+        } else {
+            (n.1 >> i) & 1
+        };
+
+        r = ((r.0 << 1) | (r.1 >> 127), (r.1 << 1) | bit);
+
+        if r >= d {
+            r = sub_u256(r, d);
+
+            if i >= 128 {
+                q.0 |= 1 << (i - 128);
+            } else {
+                q.1 |= 1 << i;
+            }
+        }
+    }
+
+    (q, r)
+}
+
+/// It divides a 256-bit integer by a 128-bit one, keeping what rounding needs of the remainder.
+///
+/// Arguments:
+///
+/// * `n`: The high and low halves of the dividend.
+/// * `d`: The divisor, must not be zero.
+/// * `sticky`: Whether digits below the dividend were already dropped.
+///
+/// Returns:
+///
+/// The quotient, how the dropped fraction compares to a half and whether it is inexact.
+fn div_half(
+    n: (u128, u128),
+    d: u128,
+    sticky: bool,
+) -> ((u128, u128), Ordering, bool) {
+    let (q, r) = div_wide(n, d);
+
+    let half = match r.cmp(&(d - r)) {
+        Ordering::Equal if sticky => Ordering::Greater,
+        half => half,
+    };
+
+    (q, half, r != 0 || sticky)
+}
+
+/// It adds a truncated quotient and its dropped fraction to an integer, rounding the sum once
+/// with the given mode.
+///
+/// Arguments:
+///
+/// * `offset`: The integer to add to.
+/// * `q`: The high and low halves of the magnitude of the truncated quotient.
+/// * `negative`: Whether the quotient is negative.
+/// * `half`: How the dropped fraction compares to a half.
+/// * `inexact`: Whether a fraction was dropped.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// `None` when the result does not fit in an i128.
+fn add_rounded(
+    offset: i128,
+    q: (u128, u128),
+    negative: bool,
+    half: Ordering,
+    inexact: bool,
+    mode: RoundingMode,
+) -> Option<i128> {
+    if q.0 != 0 {
+        return None;
+    }
+
+    let t = if negative {
+        offset.checked_sub_unsigned(q.1)?
+    } else {
+        offset.checked_add_unsigned(q.1)?
+    };
+
+    if !inexact {
+        return Some(t);
+    }
+
+    let step = if negative { -1 } else { 1 };
+
+    if t == 0 || (t < 0) == negative {
+        // the fraction takes the sum away from zero
+        if mode.round_away(t % 2, negative, half, true) {
+            t.checked_add(step)
+        } else {
+            Some(t)
+        }
+    } else {
+        // the fraction takes the sum toward zero, below the next integer toward zero
+        let toward = t + step;
+
+        if mode.round_away(toward % 2, t < 0, half.reverse(), true) {
+            Some(t)
+        } else {
+            Some(toward)
+        }
+    }
+}
+
+/// It divides a 256-bit integer by a 128-bit one and rounds the quotient with the given mode.
+///
+/// Arguments:
+///
+/// * `n`: The high and low halves of the magnitude of the dividend.
+/// * `d`: The divisor, must not be zero.
+/// * `sticky`: Whether digits below the dividend were already dropped.
+/// * `negative`: Whether the quotient is negative.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// `None` when the result does not fit in an i128.
+fn round_wide(
+    n: (u128, u128),
+    d: u128,
+    sticky: bool,
+    negative: bool,
+    mode: RoundingMode,
+) -> Option<i128> {
+    let (q, half, inexact) = div_half(n, d, sticky);
+
+    add_rounded(0, q, negative, half, inexact, mode)
+}
+
+/// It splits an integer into parts proportional to the ratios with the largest remainder method,
+/// so the parts always add up to the value. Leftover units go to the parts with the largest
+/// remainders, the first ones on a tie.
+///
+/// The ratios are taken as their exact decimals, down to the 10^-(37 - n) fraction of the
+/// largest one, where n is the number of digits in the count of ratios, so that ratios as far
+/// apart as `1e-300` and `1` still allocate.
+///
+/// Arguments:
+///
+/// * `value`: The integer to split.
+/// * `ratios`: The weights of the parts.
+///
+/// Returns:
+///
+/// `None` when a ratio is negative or not finite, or when the ratios add up to zero.
+pub(crate) fn allocate(
+    value: i128,
+    ratios: &[f64],
+) -> Option<Vec<i128>> {
+    if ratios.iter().any(|r| !r.is_finite() || *r < 0.) {
+        return None;
+    }
+
+    let decimals = ratios
+        .iter()
+        .map(|r| sci_from_f64(*r))
+        .collect::<Option<Vec<_>>>()?;
+
+    let nonzero = || decimals.iter().filter(|(m, _)| *m != 0);
+
+    // the weights stay below 10^budget, so that their sum fits
+    let budget = 37 - Digits::new(ratios.len() as u128).as_str().len() as i32;
+
+    let top = nonzero()
+        .map(|(m, e)| Digits::new(m.unsigned_abs()).as_str().len() as i32 + e)
+        .max()?;
+
+    let scale = nonzero().map(|(_, e)| *e).min()?.max(top - budget);
+
+    let weights = decimals
+        .iter()
+        .map(|(m, e)| {
+            let m = m.unsigned_abs();
+
+            if m == 0 {
+                0
+            } else if *e >= scale {
+                m * 10_u128.pow((e - scale) as u32)
+            } else {
+                10_u128.checked_pow((scale - e) as u32).map_or(0, |p| m / p)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let total = weights.iter().sum::<u128>();
+
+    let magnitude = value.unsigned_abs();
+
+    let mut parts = Vec::with_capacity(weights.len());
+    let mut remainders = Vec::with_capacity(weights.len());
+
+    for (i, w) in weights.iter().enumerate() {
+        let ((_, part), r) = div_wide(mul_wide(magnitude, *w), total);
+
+        parts.push(part);
+        remainders.push((r, i));
+    }
+
+    let leftover = magnitude - parts.iter().sum::<u128>();
+
+    remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+    for (_, i) in remainders.iter().take(leftover as usize) {
+        parts[*i] += 1;
+    }
+
+    Some(
+        parts
+            .into_iter()
+            .map(|p| if value < 0 { -(p as i128) } else { p as i128 })
+            .collect(),
+    )
+}
+
+/// It multiplies an exact decimal by another one and moves the product to the given scale, with a
+/// single rounding.
+///
+/// Arguments:
+///
+/// * `value`: The digits of the first decimal.
+/// * `from`: The number of decimal places of `value`.
+/// * `m`: The digits of the second decimal.
+/// * `m_scale`: The number of decimal places of `m`.
+/// * `to`: The number of decimal places wanted.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// `None` when the result does not fit in an i128.
+pub(crate) fn mul_decimal(
+    value: i128,
+    from: u32,
+    m: i128,
+    m_scale: u32,
+    to: u32,
+    mode: RoundingMode,
+) -> Option<i128> {
+    let scale = from.checked_add(m_scale)?;
+
+    if scale >= to {
+        mul_div_round(value, m, pow10(scale - to)?, mode)
+    } else {
+        value.checked_mul(m)?.checked_mul(pow10(to - scale)?)
+    }
+}
+
+/// It multiplies an integer by a float, treating the float as its exact decimal and rounding the
+/// product with the given mode.
+///
+/// Arguments:
+///
+/// * `value`: The integer to multiply.
+/// * `number`: The float to multiply by.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// The rounded product, saturating at the bounds of an i128.
+pub(crate) fn mul_f64(
+    value: i128,
+    number: f64,
+    mode: RoundingMode,
+) -> i128 {
+    match checked_mul_f64(value, number, mode) {
+        Ok(v) => v,
+        Err(_) => mode.round_f64(value as f64 * number) as i128,
+    }
+}
+
+/// It multiplies an integer by a float, treating the float as its exact decimal and rounding the
+/// product with the given mode.
+///
+/// Arguments:
+///
+/// * `value`: The integer to multiply.
+/// * `number`: The float to multiply by.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// The rounded product, or why it could not be computed exactly.
+pub(crate) fn checked_mul_f64(
+    value: i128,
+    number: f64,
+    mode: RoundingMode,
+) -> Result<i128, CurrencyErr> {
+    if !number.is_finite() {
+        return Err(CurrencyErr::NonFiniteNumber);
+    }
+
+    if value == 0 || number == 0. {
+        return Ok(0);
+    }
+
+    let (m, e) = sci_from_f64(number).ok_or(CurrencyErr::NonFiniteNumber)?;
+
+    let product = if e >= 0 {
+        pow10(e as u32).and_then(|p| value.checked_mul(m)?.checked_mul(p))
+    } else {
+        mul_div_pow10(value, m, e.unsigned_abs(), mode)
+    };
+
+    product.ok_or(CurrencyErr::Overflow)
+}
+
+/// It divides an integer by a float, treating the float as its exact decimal and rounding the
+/// quotient with the given mode.
+///
+/// Arguments:
+///
+/// * `value`: The integer to divide.
+/// * `number`: The float to divide by, must not be zero.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// The rounded quotient, saturating at the bounds of an i128.
+pub(crate) fn div_f64(
+    value: i128,
+    number: f64,
+    mode: RoundingMode,
+) -> i128 {
+    match checked_div_f64(value, number, mode) {
+        Ok(v) => v,
+        Err(_) => mode.round_f64(value as f64 / number) as i128,
+    }
+}
+
+/// It divides an integer by a float, treating the float as its exact decimal and rounding the
+/// quotient with the given mode.
+///
+/// Arguments:
+///
+/// * `value`: The integer to divide.
+/// * `number`: The float to divide by.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// The rounded quotient, or why it could not be computed exactly.
+pub(crate) fn checked_div_f64(
+    value: i128,
+    number: f64,
+    mode: RoundingMode,
+) -> Result<i128, CurrencyErr> {
+    if !number.is_finite() {
+        return Err(CurrencyErr::NonFiniteNumber);
+    }
+
+    if number == 0. {
+        return Err(CurrencyErr::DivisionByZero);
+    }
+
+    if value == 0 {
+        return Ok(0);
+    }
+
+    let (m, e) = sci_from_f64(number).ok_or(CurrencyErr::NonFiniteNumber)?;
+
+    let quotient = if e >= 0 {
+        let d = 10_u128
+            .checked_pow(e as u32)
+            .and_then(|p| m.unsigned_abs().checked_mul(p));
+        let negative = (value < 0) != (m < 0);
+
+        match d {
+            Some(d) => round_wide((0, value.unsigned_abs()), d, false, negative, mode),
+            // the divisor is above any u128, so the quotient is a nonzero amount below a half
+            None => round_wide((0, 0), 1, true, negative, mode),
+        }
+    } else {
+        let k = e.unsigned_abs();
+
+        match k.checked_sub(38) {
+            None | Some(0) => pow10(k).and_then(|p| mul_div_round(value, p, m, mode)),
+            Some(rest) => pow10(rest)
+                .and_then(|p| value.checked_mul(p))
+                .and_then(|v| mul_div_round(v, pow10(38)?, m, mode)),
+        }
+    };
+
+    quotient.ok_or(CurrencyErr::Overflow)
+}
+
+/// It renders a scaled integer as a plain decimal string with exactly `scale` decimal places.
+///
+/// Arguments:
+///
+/// * `value`: The scaled integer.
+/// * `scale`: The number of decimal places of `value`.
+///
+/// Returns:
+///
+/// A string such as `-1234.50`.
+pub(crate) fn to_decimal_string(
+    value: i128,
+    scale: u32,
+) -> String {
+    let digits = value.unsigned_abs().to_string();
+    let scale = scale as usize;
+
+    let digits = if digits.len() <= scale {
+        "0".repeat(scale + 1 - digits.len()) + &digits
+    } else {
+        digits
+    };
+
+    let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+
+    let sign = if value < 0 { "-" } else { "" };
+
+    if scale > 0 {
+        format!("{sign}{int_part}.{frac_part}")
+    } else {
+        format!("{sign}{int_part}")
+    }
+}
+
+/// The decimal digits of an unsigned integer, rendered on the stack.
+pub(crate) struct Digits {
+    buf: [u8; 39],
+    start: usize,
+}
+
+impl Digits {
+    pub(crate) fn new(mut value: u128) -> Self {
+        let mut buf = [b'0'; 39];
+        let mut start = buf.len();
+
+        loop {
+            start -= 1;
+            buf[start] = b'0' + (value % 10) as u8;
+            value /= 10;
+
+            if value == 0 {
+                break;
+            }
+        }
+
+        Self { buf, start }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.buf[self.start..]).expect("digits are ASCII")
+    }
+}
+
+/// It writes the absolute value of a scaled integer, grouping its integer digits, without
+/// allocating.
+///
+/// Arguments:
+///
+/// * `w`: The writer.
+/// * `value`: The scaled integer, its sign is ignored.
+/// * `scale`: The number of decimal places of the value.
+/// * `grouping`: How the integer digits are grouped.
+/// * `separator`: The group separator.
+/// * `decimal`: The decimal separator.
+pub(crate) fn write_unsigned_decimal<W: std::fmt::Write + ?Sized>(
+    w: &mut W,
+    value: i128,
+    scale: u32,
+    grouping: Grouping,
+    separator: &str,
+    decimal: &str,
+) -> std::fmt::Result {
+    let abs = value.unsigned_abs();
+
+    let (int_part, frac_part) = match pow10(scale) {
+        Some(s) => (abs / s as u128, abs % s as u128),
+        None => (0, abs),
+    };
+
+    grouping.write(Digits::new(int_part).as_str(), separator, w)?;
+
+    if scale > 0 {
+        let frac = Digits::new(frac_part);
+
+        w.write_str(decimal)?;
+
+        for _ in frac.as_str().len()..scale as usize {
+            w.write_char('0')?;
+        }
+
+        w.write_str(frac.as_str())?;
+    }
+
+    Ok(())
+}
+
+/// It rounds a scaled integer to a multiple of an increment.
+///
+/// Arguments:
+///
+/// * `value`: The scaled integer to round.
+/// * `increment`: The mantissa and scale of the value to round to, in units of one.
+/// * `scale`: The number of decimal places of `value`.
+/// * `mode`: The rounding mode.
+///
+/// Returns:
+///
+/// The scaled integer of the rounded number, or `value` unchanged when the increment is not
+/// positive.
+pub(crate) fn round_to_increment(
+    value: i128,
+    (m, s): (i128, u32),
+    scale: u32,
+    mode: RoundingMode,
+) -> i128 {
+    if m <= 0 {
+        return value;
+    }
+
+    let extra = s.saturating_sub(scale);
+
+    let (Some(step), Some(p)) = (rescale(m, s, scale + extra, mode), pow10(extra)) else {
+        return value;
+    };
+
+    match value.checked_mul(p) {
+        Some(v) => div_round(div_round(v, step, mode).saturating_mul(step), p, mode),
+        None => value,
+    }
+}
@@ -1,40 +1,157 @@
+use crate::{currency_math, CurrencyErr, Grouping, IsoCurrency, RoundingMode};
+use lazy_static::lazy_static;
+use std::sync::Arc;
+
+/// The most decimal places an i128 amount can have, 10<sup>38</sup> being the largest power of
+/// ten it holds.
+const MAX_PRECISION: u32 = 38;
+
+/// The options of a currency. They are reference counted, so cloning them to share them between
+/// many currencies is cheap, and a setter only copies them when they are shared.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "Opts", into = "Opts"))]
 pub struct CurrencyOpts {
+    inner: Arc<Opts>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+struct Opts {
     symbol: String,
     separator: String,
     decimal: String,
-    precision: f64,
+    precision: u32,
     pattern: String,
     negative_pattern: String,
     from_cents: bool,
     increment: Option<f64>,
-    use_vedic: bool,
+    /// The increment as an exact mantissa and scale, worked out once so formatting does not
+    /// have to.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    exact_increment: Option<(i128, u32)>,
+    grouping: Grouping,
     error_on_invalid: bool,
+    strict: bool,
+    code: Option<String>,
+    rounding_mode: RoundingMode,
 }
 
-impl Default for CurrencyOpts {
+impl Default for Opts {
     fn default() -> Self {
         Self {
             symbol: "$".to_string(),
             separator: ",".to_string(),
             decimal: ".".to_string(),
-            precision: 2.,
+            precision: 2,
             pattern: "!#".to_string(),
             negative_pattern: "-!#".to_string(),
             from_cents: false,
             increment: None,
-            use_vedic: false,
+            exact_increment: None,
+            grouping: Grouping::THOUSANDS,
             error_on_invalid: false,
+            strict: false,
+            code: None,
+            rounding_mode: RoundingMode::HalfAwayFromZero,
         }
     }
 }
 
+lazy_static! {
+    static ref DEFAULT_OPTS: CurrencyOpts = CurrencyOpts {
+        inner: Arc::new(Opts::default()),
+    };
+}
+
+/// The default options are built once and shared, so `None` options do not allocate.
+impl Default for CurrencyOpts {
+    fn default() -> Self {
+        DEFAULT_OPTS.clone()
+    }
+}
+
+/// Deserialized options are validated like the ones built with the setters.
+impl TryFrom<Opts> for CurrencyOpts {
+    type Error = CurrencyErr;
+
+    fn try_from(mut opts: Opts) -> Result<Self, CurrencyErr> {
+        opts.exact_increment = opts.increment.and_then(currency_math::decimal_from_f64);
+
+        let opts = Self {
+            inner: Arc::new(opts),
+        };
+
+        opts.validate()?;
+
+        Ok(opts)
+    }
+}
+
+impl From<CurrencyOpts> for Opts {
+    fn from(opts: CurrencyOpts) -> Self {
+        Arc::unwrap_or_clone(opts.inner)
+    }
+}
+
 impl CurrencyOpts {
     pub fn new() -> Self {
         Self::default()
     }
 
-    /// `pow` takes a `f64` and returns a `f64`
+    /// It creates options for an ISO 4217 currency, using its code, symbol and minor units.
+    ///
+    /// Arguments:
+    ///
+    /// * `code`: The three letter code, e.g. `"JPY"`.
+    ///
+    /// Returns:
+    ///
+    /// The options, or `None` when the code is not an active ISO 4217 code.
+    pub fn for_code(code: &str) -> Option<Self> {
+        IsoCurrency::from_code(code).map(IsoCurrency::opts)
+    }
+
+    /// It checks that the options can parse and format amounts unambiguously.
+    ///
+    /// Returns:
+    ///
+    /// `CurrencyErr::InvalidOptions` when the precision does not fit in an i128, the decimal
+    /// separator is empty or the same as the group separator, a separator contains a digit or a
+    /// minus sign, the increment is not a positive number or a pattern has no `#`.
+    pub fn validate(&self) -> Result<(), CurrencyErr> {
+        let invalid = |message: &str| Err(CurrencyErr::InvalidOptions(message.to_string()));
+
+        let numeric = |s: &str| s.contains(|c: char| c.is_ascii_digit() || c == '-');
+
+        if self.inner.precision > MAX_PRECISION {
+            invalid("precision is above 38 decimal places")
+        } else if self.inner.decimal.is_empty() {
+            invalid("decimal separator is empty")
+        } else if self.inner.decimal == self.inner.separator {
+            invalid("decimal and group separators are the same")
+        } else if numeric(&self.inner.decimal) || numeric(&self.inner.separator) {
+            invalid("separators cannot contain digits or a minus sign")
+        } else if self
+            .inner
+            .increment
+            .is_some_and(|i| !i.is_finite() || i <= 0.)
+        {
+            invalid("increment is not a positive number")
+        } else if !self.inner.pattern.contains('#') || !self.inner.negative_pattern.contains('#') {
+            invalid("pattern has no # for the amount")
+        } else {
+            Ok(())
+        }
+    }
+
+    /// It gives mutable access to the options, copying them first when they are shared.
+    fn opts_mut(&mut self) -> &mut Opts {
+        Arc::make_mut(&mut self.inner)
+    }
+
+    /// `pow` takes a `u32` and returns a `f64`
     ///
     /// Arguments:
     ///
@@ -43,8 +160,8 @@ impl CurrencyOpts {
     /// Returns:
     ///
     /// The value of 10 to the power of p.
-    fn pow(p: f64) -> f64 {
-        10_f64.powf(p)
+    fn pow(p: u32) -> f64 {
+        10_f64.powi(p as i32)
     }
 }
 
@@ -54,7 +171,7 @@ impl CurrencyOpts {
         mut self,
         symbol: impl Into<String>,
     ) -> Self {
-        self.symbol = symbol.into();
+        self.opts_mut().symbol = symbol.into();
         self
     }
 
@@ -62,7 +179,7 @@ impl CurrencyOpts {
         mut self,
         separator: impl Into<String>,
     ) -> Self {
-        self.separator = separator.into();
+        self.opts_mut().separator = separator.into();
         self
     }
 
@@ -70,15 +187,15 @@ impl CurrencyOpts {
         mut self,
         decimal: impl Into<String>,
     ) -> Self {
-        self.decimal = decimal.into();
+        self.opts_mut().decimal = decimal.into();
         self
     }
 
     pub fn set_precision(
         mut self,
-        precision: i64,
+        precision: u32,
     ) -> Self {
-        self.precision = precision as f64;
+        self.opts_mut().precision = precision;
         self
     }
 
@@ -86,7 +203,7 @@ impl CurrencyOpts {
         mut self,
         pattern: impl Into<String>,
     ) -> Self {
-        self.pattern = pattern.into();
+        self.opts_mut().pattern = pattern.into();
         self
     }
 
@@ -94,7 +211,7 @@ impl CurrencyOpts {
         mut self,
         negative_pattern: impl Into<String>,
     ) -> Self {
-        self.negative_pattern = negative_pattern.into();
+        self.opts_mut().negative_pattern = negative_pattern.into();
         self
     }
 
@@ -102,7 +219,7 @@ impl CurrencyOpts {
         mut self,
         from_cents: bool,
     ) -> Self {
-        self.from_cents = from_cents;
+        self.opts_mut().from_cents = from_cents;
         self
     }
 
@@ -110,20 +227,38 @@ impl CurrencyOpts {
         mut self,
         increment: f64,
     ) -> Self {
-        self.increment = Some(increment);
+        let opts = self.opts_mut();
+        opts.increment = Some(increment);
+        opts.exact_increment = currency_math::decimal_from_f64(increment);
         self
     }
 
     pub fn set_unset_increment(mut self) -> Self {
-        self.increment = None;
+        let opts = self.opts_mut();
+        opts.increment = None;
+        opts.exact_increment = None;
         self
     }
 
+    /// A preset for `set_grouping(Grouping::VEDIC)`, `false` goes back to groups of three when
+    /// the grouping is vedic.
     pub fn set_use_vedic(
         mut self,
         use_vedic: bool,
     ) -> Self {
-        self.use_vedic = use_vedic;
+        if use_vedic {
+            self.opts_mut().grouping = Grouping::VEDIC;
+        } else if self.inner.grouping == Grouping::VEDIC {
+            self.opts_mut().grouping = Grouping::THOUSANDS;
+        }
+        self
+    }
+
+    pub fn set_grouping(
+        mut self,
+        grouping: Grouping,
+    ) -> Self {
+        self.opts_mut().grouping = grouping;
         self
     }
 
@@ -131,54 +266,110 @@ impl CurrencyOpts {
         mut self,
         error_on_invalid: bool,
     ) -> Self {
-        self.error_on_invalid = error_on_invalid;
+        self.opts_mut().error_on_invalid = error_on_invalid;
+        self
+    }
+
+    /// Parse strings with the strict grammar, rejecting anything that is not a well formed
+    /// amount with a `CurrencyErr::ParseErr` that carries the byte offset and the kind of
+    /// violation.
+    pub fn set_strict(
+        mut self,
+        strict: bool,
+    ) -> Self {
+        self.opts_mut().strict = strict;
+        self
+    }
+
+    /// It sets the currency code, e.g. `"USD"`, which is stored in upper case. Arithmetic
+    /// between two currencies with different codes is rejected.
+    pub fn set_code(
+        mut self,
+        code: impl Into<String>,
+    ) -> Self {
+        self.opts_mut().code = Some(code.into().trim().to_ascii_uppercase());
+        self
+    }
+
+    pub fn set_unset_code(mut self) -> Self {
+        self.opts_mut().code = None;
+        self
+    }
+
+    pub fn set_rounding_mode(
+        mut self,
+        rounding_mode: RoundingMode,
+    ) -> Self {
+        self.opts_mut().rounding_mode = rounding_mode;
         self
     }
 }
 
 // GETTERS
 impl CurrencyOpts {
-    pub fn symbol(&self) -> String {
-        self.symbol.clone()
+    pub fn symbol(&self) -> &str {
+        &self.inner.symbol
     }
 
-    pub fn separator(&self) -> String {
-        self.separator.clone()
+    pub fn separator(&self) -> &str {
+        &self.inner.separator
     }
 
-    pub fn decimal(&self) -> String {
-        self.decimal.clone()
+    pub fn decimal(&self) -> &str {
+        &self.inner.decimal
     }
 
-    pub fn precision(&self) -> f64 {
-        self.precision
+    pub fn precision(&self) -> u32 {
+        self.inner.precision
     }
 
-    pub fn pattern(&self) -> String {
-        self.pattern.clone()
+    pub fn pattern(&self) -> &str {
+        &self.inner.pattern
     }
 
-    pub fn negative_pattern(&self) -> String {
-        self.negative_pattern.clone()
+    pub fn negative_pattern(&self) -> &str {
+        &self.inner.negative_pattern
     }
 
     pub fn from_cents(&self) -> bool {
-        self.from_cents
+        self.inner.from_cents
     }
 
     pub fn increment(&self) -> f64 {
-        if let Some(inc) = self.increment {
+        if let Some(inc) = self.inner.increment {
             inc
         } else {
-            1. / Self::pow(self.precision)
+            1. / Self::pow(self.inner.precision)
         }
     }
 
+    /// It returns the increment set with `set_increment` as an exact mantissa and scale, or
+    /// `None` when amounts only round to the precision.
+    pub(crate) fn exact_increment(&self) -> Option<(i128, u32)> {
+        self.inner.exact_increment
+    }
+
     pub fn use_vedic(&self) -> bool {
-        self.use_vedic
+        self.inner.grouping == Grouping::VEDIC
+    }
+
+    pub fn grouping(&self) -> Grouping {
+        self.inner.grouping
     }
 
     pub fn error_on_invalid(&self) -> bool {
-        self.error_on_invalid
+        self.inner.error_on_invalid
+    }
+
+    pub fn strict(&self) -> bool {
+        self.inner.strict
+    }
+
+    pub fn code(&self) -> Option<&str> {
+        self.inner.code.as_deref()
+    }
+
+    pub fn rounding_mode(&self) -> RoundingMode {
+        self.inner.rounding_mode
     }
 }
@@ -0,0 +1,399 @@
+use crate::{CurrencyOpts, Grouping, IsoCurrency, ParseErrKind, ParseError};
+
+/// A strict parser for amounts written with the conventions of a set of options.
+///
+/// The grammar is, with optional whitespace between the parts:
+///
+/// `[(] [sign] [symbol] [code] [sign] digits [separator digits]* [decimal digits] [symbol] [code] [)]`
+///
+/// The symbol and the code may come before or after the number, at most once each, and there
+/// is at most one sign. Parentheses mean a negative amount. Group separators have to follow the
+/// grouping of the options.
+pub(crate) struct StrictParser<'a> {
+    input: &'a str,
+    pos: usize,
+    symbol: &'a str,
+    separator: &'a str,
+    decimal: &'a str,
+    code: Option<&'a str>,
+    grouping: Grouping,
+}
+
+/// The amount a strict parse produced.
+pub(crate) struct Parsed {
+    /// The amount as a plain decimal string, e.g. `-1234.5`.
+    pub(crate) value: String,
+    /// The byte offset of the first digit.
+    pub(crate) offset: usize,
+}
+
+impl<'a> StrictParser<'a> {
+    pub(crate) fn new(
+        input: &'a str,
+        opts: &'a CurrencyOpts,
+    ) -> Self {
+        Self {
+            input,
+            pos: 0,
+            symbol: opts.symbol(),
+            separator: opts.separator(),
+            decimal: opts.decimal(),
+            code: opts.code(),
+            grouping: opts.grouping(),
+        }
+    }
+
+    /// It parses the whole input.
+    ///
+    /// Returns:
+    ///
+    /// The amount, or the first violation of the grammar with its byte offset.
+    pub(crate) fn parse(mut self) -> Result<Parsed, ParseError> {
+        self.skip_whitespace();
+
+        if self.rest().is_empty() {
+            return Err(self.error(ParseErrKind::Empty, "no amount"));
+        }
+
+        let parenthesis = self.eat("(");
+
+        let mut negative = parenthesis;
+        let mut sign = false;
+        let mut symbol = false;
+        let mut code = false;
+
+        loop {
+            self.skip_whitespace();
+
+            if self.rest().starts_with(['-', '+']) {
+                if sign || parenthesis {
+                    return Err(self.error(ParseErrKind::MisplacedSign, "unexpected sign"));
+                }
+
+                sign = true;
+                negative = self.rest().starts_with('-');
+                self.pos += 1;
+            } else if !symbol && self.eat_symbol() {
+                symbol = true;
+            } else if !code && self.eat_code()? {
+                code = true;
+            } else {
+                break;
+            }
+        }
+
+        let offset = self.pos;
+
+        let (integer, fraction) = self.number()?;
+
+        loop {
+            self.skip_whitespace();
+
+            if self.rest().starts_with(['-', '+']) {
+                return Err(self.error(ParseErrKind::MisplacedSign, "sign after the number"));
+            } else if !symbol && self.eat_symbol() {
+                symbol = true;
+            } else if !code && self.eat_code()? {
+                code = true;
+            } else {
+                break;
+            }
+        }
+
+        if parenthesis && !self.eat(")") {
+            return Err(self.error(
+                ParseErrKind::UnbalancedParenthesis,
+                "missing closing parenthesis",
+            ));
+        }
+
+        self.skip_whitespace();
+
+        if let Some(c) = self.rest().chars().next() {
+            return Err(self.error(
+                ParseErrKind::UnexpectedChar,
+                format!("unexpected character {c:?}"),
+            ));
+        }
+
+        let mut value = if negative {
+            format!("-{integer}")
+        } else {
+            integer
+        };
+
+        if !fraction.is_empty() {
+            value = value + "." + &fraction;
+        }
+
+        Ok(Parsed { value, offset })
+    }
+
+    /// It reads the digits of the number, checking the groups and the decimal separator.
+    fn number(&mut self) -> Result<(String, String), ParseError> {
+        let start = self.pos;
+
+        let first = self.digits();
+
+        if first.is_empty() {
+            return Err(match self.rest().chars().next() {
+                None => self.error(ParseErrKind::Empty, "no amount"),
+                Some(_) if self.at(self.decimal) => {
+                    self.error(ParseErrKind::MissingDigits, "no digits before the decimal")
+                }
+                Some(c) => self.error(
+                    ParseErrKind::UnexpectedChar,
+                    format!("unexpected character {c:?}"),
+                ),
+            });
+        }
+
+        let mut integer = first.to_string();
+        let mut groups = vec![(start, first.len())];
+
+        while self.at(self.separator) && self.followed_by_digit(self.separator) {
+            let separator = self.pos;
+
+            self.pos += self.separator.len();
+
+            let group = self.digits();
+
+            integer.push_str(group);
+            groups.push((separator, group.len()));
+        }
+
+        self.check_groups(&groups)?;
+
+        let mut fraction = String::new();
+
+        if self.at(self.decimal) {
+            self.pos += self.decimal.len();
+
+            fraction = self.digits().to_string();
+
+            if fraction.is_empty() {
+                return Err(self.error(ParseErrKind::MissingDigits, "no digits after the decimal"));
+            }
+
+            if self.at(self.decimal) {
+                return Err(self.error(ParseErrKind::MultipleDecimals, "second decimal separator"));
+            }
+
+            if self.at(self.separator) && self.followed_by_digit(self.separator) {
+                return Err(self.error(ParseErrKind::InvalidGroup, "separator after the decimal"));
+            }
+        }
+
+        Ok((integer, fraction))
+    }
+
+    /// It checks the sizes of the digit groups against the grouping, the first group being the
+    /// one furthest from the decimal separator.
+    ///
+    /// Arguments:
+    ///
+    /// * `groups`: The byte offset the group starts at, or of its separator, and its length.
+    fn check_groups(
+        &self,
+        groups: &[(usize, usize)],
+    ) -> Result<(), ParseError> {
+        if groups.len() < 2 {
+            return Ok(());
+        }
+
+        let primary = self.grouping.primary() as usize;
+        let secondary = self.grouping.secondary() as usize;
+
+        let invalid = |offset: usize| {
+            Err(ParseError::new(
+                ParseErrKind::InvalidGroup,
+                Some(offset),
+                format!("group separator does not match the grouping at byte {offset}"),
+            ))
+        };
+
+        if primary == 0 {
+            return invalid(groups[1].0);
+        }
+
+        let last = groups.len() - 1;
+
+        for (i, (offset, len)) in groups.iter().enumerate() {
+            let valid = match i {
+                0 => (1..=secondary).contains(len),
+                i if i == last => *len == primary,
+                _ => *len == secondary,
+            };
+
+            if !valid {
+                return invalid(*offset);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// It consumes the symbol of the options when the input continues with it.
+    fn eat_symbol(&mut self) -> bool {
+        let symbol = self.symbol;
+
+        self.eat(symbol)
+    }
+
+    /// It consumes a three letter currency code when the input continues with one.
+    ///
+    /// Returns:
+    ///
+    /// Whether a code was consumed, or an error when it is not the code of the options or not an
+    /// ISO 4217 code.
+    fn eat_code(&mut self) -> Result<bool, ParseError> {
+        let rest = self.rest();
+
+        let len = rest.bytes().take_while(|b| b.is_ascii_alphabetic()).count();
+
+        if len != 3 {
+            return Ok(false);
+        }
+
+        let found = &rest[..3];
+
+        match self.code {
+            Some(code) if !code.eq_ignore_ascii_case(found) => Err(self.error(
+                ParseErrKind::CodeMismatch,
+                format!("expected the code {code}, found {found}"),
+            )),
+            None if IsoCurrency::from_code(found).is_none() => Err(self.error(
+                ParseErrKind::UnknownCode,
+                format!("unknown currency code {found}"),
+            )),
+            _ => {
+                self.pos += 3;
+                Ok(true)
+            }
+        }
+    }
+
+    /// It consumes the ASCII digits at the current position.
+    fn digits(&mut self) -> &'a str {
+        let input = self.input;
+        let rest = &input[self.pos..];
+
+        let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+
+        self.pos += len;
+
+        &rest[..len]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(
+        &mut self,
+        s: &str,
+    ) -> bool {
+        let found = self.at(s);
+
+        if found {
+            self.pos += s.len();
+        }
+
+        found
+    }
+
+    fn at(
+        &self,
+        s: &str,
+    ) -> bool {
+        !s.is_empty() && self.rest().starts_with(s)
+    }
+
+    fn followed_by_digit(
+        &self,
+        s: &str,
+    ) -> bool {
+        self.rest()[s.len()..].starts_with(|c: char| c.is_ascii_digit())
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn error(
+        &self,
+        kind: ParseErrKind,
+        message: impl Into<String>,
+    ) -> ParseError {
+        let message = message.into();
+
+        ParseError::new(
+            kind,
+            Some(self.pos),
+            format!("{message} at byte {}", self.pos),
+        )
+    }
+}
+
+/// It reads the amount of a string the lenient way, without regular expressions: an amount in
+/// parentheses is negative, every character other than digits, `-` and the decimal separator is
+/// dropped, and the decimal separator becomes `.`.
+///
+/// Arguments:
+///
+/// * `value`: The string to read, e.g. `"($1,234.50)"`.
+/// * `decimal`: The decimal separator of the options.
+///
+/// Returns:
+///
+/// The plain decimal string, e.g. `"-1234.50"`, which may still be invalid.
+#[cfg_attr(feature = "fancy-regex", allow(dead_code))]
+pub(crate) fn lenient_number(
+    value: &str,
+    decimal: &str,
+) -> String {
+    let parentheses = parentheses(value);
+
+    let mut decimal_chars = decimal.chars();
+
+    let single_decimal = match (decimal_chars.next(), decimal_chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    };
+
+    let mut number = String::with_capacity(value.len() + 1);
+
+    for (i, c) in value.char_indices() {
+        match parentheses {
+            Some((open, _)) if i == open => number.push('-'),
+            Some((_, close)) if i == close => {}
+            _ if c.is_ascii_digit() || c == '-' => number.push(c),
+            _ if single_decimal == Some(c) => number.push('.'),
+            _ if single_decimal.is_none() && decimal.contains(c) => number.push(c),
+            _ => {}
+        }
+    }
+
+    if single_decimal.is_none() && !decimal.is_empty() {
+        number = number.replace(decimal, ".");
+    }
+
+    number
+}
+
+/// It finds the first opening parenthesis that has a closing one after it on the same line,
+/// pairing it with the last such closing parenthesis.
+///
+/// Returns:
+///
+/// The byte offsets of both parentheses.
+fn parentheses(value: &str) -> Option<(usize, usize)> {
+    value.match_indices('(').find_map(|(open, _)| {
+        let rest = &value[open + 1..];
+        let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+
+        line.rfind(')').map(|close| (open, open + 1 + close))
+    })
+}
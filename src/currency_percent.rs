@@ -0,0 +1,339 @@
+use crate::{currency_math, Backend, Currency, CurrencyErr, Decimal};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The number of decimal places a computed percentage is rounded to.
+const PERCENT_SCALE: u32 = 18;
+
+/// A percentage, kept as an exact decimal so that `Percentage::new(12.5)` is exactly twelve and
+/// a half percent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Percentage(Decimal);
+
+/// A number of basis points, a hundredth of a percent each, so `BasisPoints::new(125.)` is
+/// `1.25%`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BasisPoints(Decimal);
+
+impl Percentage {
+    /// It creates a percentage from the shortest representation of a float, so `0.1` is exactly
+    /// a tenth of a percent.
+    ///
+    /// Arguments:
+    ///
+    /// * `value`: The percentage, e.g. `12.5` for `12.5%`.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the Percentage struct, or `CurrencyErr::NonFiniteNumber`.
+    pub fn new(value: f64) -> Result<Self, CurrencyErr> {
+        Decimal::from_f64(value)
+            .map(Self)
+            .ok_or(CurrencyErr::NonFiniteNumber)
+    }
+
+    /// It creates a percentage from an exact decimal, e.g. `Decimal::new(125, 1)` for `12.5%`.
+    pub fn from_decimal(value: Decimal) -> Self {
+        Self(value)
+    }
+
+    /// It returns the percentage as an exact decimal, `12.5` for `12.5%`.
+    pub fn value(&self) -> Decimal {
+        self.0
+    }
+
+    /// It returns the fraction the percentage stands for, `0.125` for `12.5%`.
+    pub fn ratio(&self) -> Decimal {
+        Decimal::new(self.0.mantissa(), self.0.scale() + 2)
+    }
+
+    /// It returns the closest float to the percentage.
+    pub fn to_f64(&self) -> f64 {
+        Decimal::to_f64(&self.0)
+    }
+}
+
+impl BasisPoints {
+    /// It creates a number of basis points from the shortest representation of a float.
+    ///
+    /// Arguments:
+    ///
+    /// * `value`: The basis points, e.g. `125.` for `1.25%`.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the BasisPoints struct, or `CurrencyErr::NonFiniteNumber`.
+    pub fn new(value: f64) -> Result<Self, CurrencyErr> {
+        Decimal::from_f64(value)
+            .map(Self)
+            .ok_or(CurrencyErr::NonFiniteNumber)
+    }
+
+    /// It creates a number of basis points from an exact decimal.
+    pub fn from_decimal(value: Decimal) -> Self {
+        Self(value)
+    }
+
+    /// It returns the basis points as an exact decimal.
+    pub fn value(&self) -> Decimal {
+        self.0
+    }
+
+    /// It returns the closest float to the basis points.
+    pub fn to_f64(&self) -> f64 {
+        Decimal::to_f64(&self.0)
+    }
+}
+
+impl From<BasisPoints> for Percentage {
+    fn from(bp: BasisPoints) -> Self {
+        Self(Decimal::new(bp.0.mantissa(), bp.0.scale() + 2).normalize())
+    }
+}
+
+/// Saturates when the basis points do not fit in an i128.
+impl From<Percentage> for BasisPoints {
+    fn from(pct: Percentage) -> Self {
+        let (mantissa, scale) = match pct.0.scale().checked_sub(2) {
+            Some(scale) => (pct.0.mantissa(), scale),
+            None => (
+                pct.0
+                    .mantissa()
+                    .saturating_mul(currency_math::pow10(2 - pct.0.scale()).unwrap_or(1)),
+                0,
+            ),
+        };
+
+        Self(Decimal::new(mantissa, scale))
+    }
+}
+
+impl Display for Percentage {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "{}%", self.0.normalize())
+    }
+}
+
+impl Display for BasisPoints {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "{} bp", self.0.normalize())
+    }
+}
+
+/// It parses a plain decimal, with or without a trailing `%`, e.g. `"12.5%"`.
+impl FromStr for Percentage {
+    type Err = CurrencyErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        s.strip_suffix('%')
+            .unwrap_or(s)
+            .trim_end()
+            .parse()
+            .map(Self)
+    }
+}
+
+/// It parses a plain decimal, with or without a trailing `bp`, e.g. `"125 bp"`.
+impl FromStr for BasisPoints {
+    type Err = CurrencyErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        s.strip_suffix("bp")
+            .unwrap_or(s)
+            .trim_end()
+            .parse()
+            .map(Self)
+    }
+}
+
+impl<B: Backend> Currency<B> {
+    /// > Computes a percentage of the current instance, e.g. the tax or the discount
+    ///
+    /// > Currency::new_float(80., None).percent_of(Percentage::new(12.5)?)?.value(); // 10.00
+    ///
+    /// Arguments:
+    ///
+    /// * `pct`: A `Percentage` or `BasisPoints`.
+    ///
+    /// Returns:
+    ///
+    /// A new instance rounded once with the rounding mode of the options, or
+    /// `CurrencyErr::Overflow`.
+    pub fn percent_of(
+        &self,
+        pct: impl Into<Percentage>,
+    ) -> Result<Self, CurrencyErr> {
+        let ratio = pct.into().ratio();
+
+        self.scale_by(ratio.mantissa(), ratio.scale(), 0)
+    }
+
+    /// > Adds a percentage of the current instance to it, e.g. a surcharge
+    ///
+    /// Arguments:
+    ///
+    /// * `pct`: A `Percentage` or `BasisPoints`.
+    ///
+    /// Returns:
+    ///
+    /// `value * (1 + pct / 100)` rounded once with the rounding mode of the options, or
+    /// `CurrencyErr::Overflow`.
+    pub fn add_percent(
+        &self,
+        pct: impl Into<Percentage>,
+    ) -> Result<Self, CurrencyErr> {
+        let ratio = pct.into().ratio();
+
+        self.scale_by(ratio.mantissa(), ratio.scale(), 1)
+    }
+
+    /// > Subtracts a percentage of the current instance from it, e.g. a discount
+    ///
+    /// Arguments:
+    ///
+    /// * `pct`: A `Percentage` or `BasisPoints`.
+    ///
+    /// Returns:
+    ///
+    /// `value * (1 - pct / 100)` rounded once with the rounding mode of the options, or
+    /// `CurrencyErr::Overflow`.
+    pub fn subtract_percent(
+        &self,
+        pct: impl Into<Percentage>,
+    ) -> Result<Self, CurrencyErr> {
+        let ratio = pct.into().ratio();
+
+        self.scale_by(
+            ratio
+                .mantissa()
+                .checked_neg()
+                .ok_or(CurrencyErr::Overflow)?,
+            ratio.scale(),
+            1,
+        )
+    }
+
+    /// > Computes the price of the current instance as a cost with a markup on it
+    ///
+    /// A markup of 25% on a cost of 80.00 gives a price of 100.00.
+    ///
+    /// Arguments:
+    ///
+    /// * `pct`: The markup, a `Percentage` or `BasisPoints` of the cost.
+    ///
+    /// Returns:
+    ///
+    /// `cost * (1 + pct / 100)` rounded once, or `CurrencyErr::Overflow`.
+    pub fn markup(
+        &self,
+        pct: impl Into<Percentage>,
+    ) -> Result<Self, CurrencyErr> {
+        self.add_percent(pct)
+    }
+
+    /// > Computes the price of the current instance as a cost with a margin on the price
+    ///
+    /// A margin of 20% on a cost of 80.00 gives a price of 100.00.
+    ///
+    /// Arguments:
+    ///
+    /// * `pct`: The margin, a `Percentage` or `BasisPoints` of the price.
+    ///
+    /// Returns:
+    ///
+    /// `cost / (1 - pct / 100)` rounded once, `CurrencyErr::DivisionByZero` for a margin of
+    /// 100%, or `CurrencyErr::Overflow`.
+    pub fn margin(
+        &self,
+        pct: impl Into<Percentage>,
+    ) -> Result<Self, CurrencyErr> {
+        let ratio = pct.into().ratio();
+
+        if currency_math::pow10(ratio.scale()) == Some(ratio.mantissa()) {
+            return Err(CurrencyErr::DivisionByZero);
+        }
+
+        let minor = currency_math::div_one_minus_pow10(
+            self.minor_units(),
+            ratio.mantissa(),
+            ratio.scale(),
+            self.opts.rounding_mode(),
+        )
+        .ok_or(CurrencyErr::Overflow)?;
+
+        self.with_minor(minor)
+    }
+
+    /// > Computes the change from one amount to another as a percentage of the first
+    ///
+    /// Arguments:
+    ///
+    /// * `from`: The amount before.
+    /// * `to`: The amount after.
+    ///
+    /// Returns:
+    ///
+    /// The change, rounded to 18 decimal places with the rounding mode of `from`,
+    /// `CurrencyErr::CurrencyMismatch` when the amounts have different codes,
+    /// `CurrencyErr::DivisionByZero` when `from` is zero, or `CurrencyErr::Overflow`.
+    pub fn percent_change(
+        from: &Self,
+        to: &Self,
+    ) -> Result<Percentage, CurrencyErr> {
+        from.check_code(to)?;
+
+        if from.is_zero() {
+            return Err(CurrencyErr::DivisionByZero);
+        }
+
+        let scale = from.precision().max(to.precision());
+        let mode = from.opts.rounding_mode();
+
+        let rescale = |cur: &Self| {
+            currency_math::rescale(cur.minor_units(), cur.precision(), scale, mode)
+                .ok_or(CurrencyErr::Overflow)
+        };
+
+        let (before, after) = (rescale(from)?, rescale(to)?);
+
+        let change = after.checked_sub(before).ok_or(CurrencyErr::Overflow)?;
+
+        let hundred = currency_math::pow10(PERCENT_SCALE + 2).ok_or(CurrencyErr::Overflow)?;
+
+        let mantissa = currency_math::mul_div_round(change, hundred, before, mode)
+            .ok_or(CurrencyErr::Overflow)?;
+
+        Ok(Percentage(
+            Decimal::new(mantissa, PERCENT_SCALE).normalize(),
+        ))
+    }
+
+    /// It multiplies the current instance by `base + m / 10^scale` with a single rounding.
+    fn scale_by(
+        &self,
+        m: i128,
+        scale: u32,
+        base: i128,
+    ) -> Result<Self, CurrencyErr> {
+        let minor = currency_math::mul_add_div_pow10(
+            self.minor_units(),
+            base,
+            m,
+            scale,
+            self.opts.rounding_mode(),
+        )
+        .ok_or(CurrencyErr::Overflow)?;
+
+        self.with_minor(minor)
+    }
+}
@@ -0,0 +1,345 @@
+use crate::{CurrencyErr, Decimal, ExchangeRate, ParseError, RateProvider, RateTable};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::str::FromStr;
+
+/// A calendar day rates are published for, written `YYYY-MM-DD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RateDate {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl RateDate {
+    /// It creates a new date.
+    ///
+    /// Arguments:
+    ///
+    /// * `year`: The year, e.g. `2024`.
+    /// * `month`: The month, from `1` to `12`.
+    /// * `day`: The day of the month, starting at `1`.
+    ///
+    /// Returns:
+    ///
+    /// `None` when the day does not exist.
+    pub fn new(
+        year: u16,
+        month: u8,
+        day: u8,
+    ) -> Option<Self> {
+        // `is_multiple_of` would need Rust 1.87
+        #[allow(clippy::manual_is_multiple_of)]
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+
+        let days = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+
+        (1..=days)
+            .contains(&day)
+            .then_some(Self { year, month, day })
+    }
+}
+
+// GETTERS
+impl RateDate {
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+impl Display for RateDate {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for RateDate {
+    type Err = CurrencyErr;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || CurrencyErr::invalid(format!("invalid date: {value:?}"));
+
+        let mut parts = value.trim().splitn(3, '-');
+
+        let mut next = |len: usize| {
+            parts
+                .next()
+                .filter(|p| p.len() == len && p.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|p| p.parse::<u16>().ok())
+                .ok_or_else(invalid)
+        };
+
+        let (year, month, day) = (next(4)?, next(2)?, next(2)?);
+
+        Self::new(year, month as u8, day as u8).ok_or_else(invalid)
+    }
+}
+
+/// Exchange rate tables indexed by the date they were published for.
+///
+/// Rates are looked up in the latest table published on or before the date asked for, so a
+/// weekend or a holiday uses the rates of the last business day.
+#[derive(Debug, Clone, Default)]
+pub struct DatedRateTable {
+    base: Option<String>,
+    tables: BTreeMap<RateDate, RateTable>,
+}
+
+impl DatedRateTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// It reads the European Central Bank euro reference rates, either the daily or the
+    /// historical XML file. The tables triangulate through `EUR`.
+    ///
+    /// Arguments:
+    ///
+    /// * `reader`: The XML source.
+    ///
+    /// Returns:
+    ///
+    /// The tables, or the reason the rates could not be read.
+    pub fn from_ecb_xml<R: Read>(mut reader: R) -> Result<Self, CurrencyErr> {
+        let mut xml = String::new();
+
+        reader
+            .read_to_string(&mut xml)
+            .map_err(|e| CurrencyErr::io(None, e))?;
+
+        let mut table = Self::new().set_base("EUR");
+        let mut date = None;
+
+        for tag in xml.split('<').filter(|t| t.starts_with("Cube")) {
+            if let Some(time) = xml_attr(tag, "time") {
+                date = Some(time.parse()?);
+            }
+
+            if let (Some(currency), Some(rate)) = (xml_attr(tag, "currency"), xml_attr(tag, "rate"))
+            {
+                let date = date.ok_or_else(|| {
+                    CurrencyErr::invalid(format!("rate for {currency} has no date"))
+                })?;
+
+                table.insert(date, ExchangeRate::new("EUR", currency, rate.parse()?)?);
+            }
+        }
+
+        Ok(table)
+    }
+
+    /// It reads the European Central Bank euro reference rates from an XML file.
+    ///
+    /// Arguments:
+    ///
+    /// * `path`: The path of the file.
+    ///
+    /// Returns:
+    ///
+    /// The tables, or the reason the rates could not be read.
+    pub fn from_ecb_xml_file(path: impl AsRef<Path>) -> Result<Self, CurrencyErr> {
+        Self::from_ecb_xml(open(path.as_ref())?)
+    }
+
+    /// It reads rates from CSV lines of `date,base,quote,rate`, e.g.
+    /// `2024-01-02,EUR,USD,1.0956`. A header line starting with `date` and blank lines are
+    /// skipped. The tables triangulate through the base when every line has the same one.
+    ///
+    /// Arguments:
+    ///
+    /// * `reader`: The CSV source.
+    ///
+    /// Returns:
+    ///
+    /// The tables, or the reason the rates could not be read, with its line number.
+    pub fn from_csv<R: Read>(mut reader: R) -> Result<Self, CurrencyErr> {
+        let mut csv = String::new();
+
+        reader
+            .read_to_string(&mut csv)
+            .map_err(|e| CurrencyErr::io(None, e))?;
+
+        let mut table = Self::new();
+        let mut bases = Vec::new();
+
+        for (i, line) in csv.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || (i == 0 && line.to_ascii_lowercase().starts_with("date")) {
+                continue;
+            }
+
+            let at_line = |e: CurrencyErr| match e {
+                CurrencyErr::ParseErr(e) => CurrencyErr::ParseErr(ParseError::new(
+                    e.kind(),
+                    e.offset(),
+                    format!("line {}: {}", i + 1, e.message()),
+                )),
+                e => e,
+            };
+
+            let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+
+            let [date, base, quote, rate] = fields[..] else {
+                return Err(CurrencyErr::invalid(format!(
+                    "line {}: expected date,base,quote,rate",
+                    i + 1
+                )));
+            };
+
+            let rate = ExchangeRate::new(base, quote, rate.parse::<Decimal>().map_err(at_line)?)
+                .map_err(at_line)?;
+
+            if !bases.contains(&rate.from()) {
+                bases.push(rate.from());
+            }
+
+            table.insert(date.parse().map_err(at_line)?, rate);
+        }
+
+        Ok(match &bases[..] {
+            [base] => table.set_base(base),
+            _ => table,
+        })
+    }
+
+    /// It reads rates from a CSV file of `date,base,quote,rate` lines.
+    ///
+    /// Arguments:
+    ///
+    /// * `path`: The path of the file.
+    ///
+    /// Returns:
+    ///
+    /// The tables, or the reason the rates could not be read.
+    pub fn from_csv_file(path: impl AsRef<Path>) -> Result<Self, CurrencyErr> {
+        Self::from_csv(open(path.as_ref())?)
+    }
+
+    /// It adds a rate to the table of the given date.
+    ///
+    /// Arguments:
+    ///
+    /// * `date`: The date the rate was published for.
+    /// * `rate`: The rate to add.
+    pub fn insert(
+        &mut self,
+        date: RateDate,
+        rate: ExchangeRate,
+    ) {
+        let base = self.base.clone();
+
+        self.tables
+            .entry(date)
+            .or_insert_with(|| match base {
+                Some(base) => RateTable::new().set_base(&base),
+                None => RateTable::new(),
+            })
+            .insert(rate);
+    }
+
+    /// It returns the table in effect on a date, the latest one published on or before it.
+    ///
+    /// Arguments:
+    ///
+    /// * `date`: The date of the conversion.
+    ///
+    /// Returns:
+    ///
+    /// The table, or `None` when no rates were published before the date.
+    pub fn on(
+        &self,
+        date: RateDate,
+    ) -> Option<&RateTable> {
+        self.tables.range(..=date).next_back().map(|(_, t)| t)
+    }
+
+    /// It looks up the rate in effect on a date.
+    ///
+    /// Arguments:
+    ///
+    /// * `date`: The date of the conversion.
+    /// * `from`: The code of the currency being converted.
+    /// * `to`: The code of the currency converted into.
+    ///
+    /// Returns:
+    ///
+    /// The rate, or `None` when it is not known on that date.
+    pub fn rate_on(
+        &self,
+        date: RateDate,
+        from: &str,
+        to: &str,
+    ) -> Option<ExchangeRate> {
+        self.on(date)?.rate(from, to)
+    }
+}
+
+// SETTERS
+impl DatedRateTable {
+    /// The currency rates are triangulated through when there is no direct rate.
+    pub fn set_base(
+        mut self,
+        base: &str,
+    ) -> Self {
+        self.base = Some(base.trim().to_ascii_uppercase());
+        self.tables = self
+            .tables
+            .into_iter()
+            .map(|(date, table)| (date, table.set_base(base)))
+            .collect();
+        self
+    }
+}
+
+// GETTERS
+impl DatedRateTable {
+    pub fn base(&self) -> Option<String> {
+        self.base.clone()
+    }
+
+    /// The dates rates were published for, in order.
+    pub fn dates(&self) -> Vec<RateDate> {
+        self.tables.keys().copied().collect()
+    }
+}
+
+/// It opens a rate file for buffered reading.
+fn open(path: &Path) -> Result<BufReader<File>, CurrencyErr> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| CurrencyErr::io(Some(path.display().to_string()), e))
+}
+
+/// It returns the value of an attribute of an XML tag, quoted with `'` or `"`.
+fn xml_attr<'a>(
+    tag: &'a str,
+    name: &str,
+) -> Option<&'a str> {
+    tag.split_whitespace().find_map(|part| {
+        let value = part.strip_prefix(name)?.strip_prefix('=')?;
+        let quote = value.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+
+        value[1..].split(quote).next()
+    })
+}
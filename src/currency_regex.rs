@@ -1,56 +1,33 @@
 use fancy_regex::Regex;
 use lazy_static::lazy_static;
-
-fn format_dollar_group(
-    dollar: String,
-    separator: String,
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// It replaces the matches of a pattern built from the decimal separator, compiling the pattern
+/// once per separator.
+fn replace_cached(
+    pattern: String,
+    value: &str,
+    replacement: &str,
 ) -> String {
     lazy_static! {
-        static ref GROUP_REGEX: Regex = Regex::new(r"(\d)(?=(\d{3})+\b)").unwrap();
+        static ref CACHE: Mutex<HashMap<String, Regex>> = Mutex::new(HashMap::new());
     }
 
-    let replace = format!("$1{separator}");
-
-    GROUP_REGEX.replace_all(&dollar, replace).to_string()
-}
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
 
-fn format_dollar_vedic(
-    dollar: String,
-    separator: String,
-) -> String {
-    lazy_static! {
-        static ref VEDIC_REGEX: Regex = Regex::new(r"(\d)(?=(\d\d)+\d\b)").unwrap();
-    }
+    let regex = cache
+        .entry(pattern)
+        .or_insert_with_key(|pattern| Regex::new(pattern).unwrap());
 
-    let replace = format!("$1{separator}");
-
-    VEDIC_REGEX.replace_all(&dollar, replace).to_string()
-}
-
-pub fn format_dollar(
-    use_vedic: bool,
-    dollar: String,
-    separator: String,
-) -> String {
-    if use_vedic {
-        return format_dollar_vedic(dollar, separator);
-    }
-
-    format_dollar_group(dollar, separator)
+    regex.replace_all(value, replacement).to_string()
 }
 
 pub fn replace_any_non_numeric_values(
-    decimal: String,
-    value_allow_negative: String,
+    decimal: &str,
+    value_allow_negative: &str,
 ) -> String {
-    lazy_static! {
-        static ref REGEX: fn(String) -> Regex =
-            |decimal: String| Regex::new(&format!(r"[^-\d{decimal}]")).unwrap();
-    }
-
-    REGEX(decimal)
-        .replace_all(&value_allow_negative, "")
-        .to_string()
+    replace_cached(format!(r"[^-\d{decimal}]"), value_allow_negative, "")
 }
 
 pub fn allow_negative_values(value: &str) -> String {
@@ -62,13 +39,21 @@ pub fn allow_negative_values(value: &str) -> String {
 }
 
 pub fn convert_any_decimal_values(
-    decimal: String,
-    numeric_values: String,
+    decimal: &str,
+    numeric_values: &str,
 ) -> String {
-    lazy_static! {
-        static ref REGEX: fn(String) -> Regex =
-            |decimal: String| Regex::new(&("\\".to_string() + &decimal)).unwrap();
-    }
+    replace_cached("\\".to_string() + decimal, numeric_values, ".")
+}
+
+/// The regular expression implementation of `currency_parser::lenient_number`, which keeps
+/// any Unicode digit.
+pub(crate) fn lenient_number(
+    value: &str,
+    decimal: &str,
+) -> String {
+    let value_allow_negative = allow_negative_values(value);
+
+    let numeric_values = replace_any_non_numeric_values(decimal, &value_allow_negative);
 
-    REGEX(decimal).replace_all(&numeric_values, ".").to_string()
+    convert_any_decimal_values(decimal, &numeric_values)
 }
@@ -0,0 +1,88 @@
+use std::cmp::Ordering;
+
+/// How a value that falls between two minor units is rounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RoundingMode {
+    /// Ties round away from zero, `1.5` to `2` and `-1.5` to `-2`.
+    #[default]
+    HalfAwayFromZero,
+    /// Ties round to the even neighbour (banker's rounding), `2.5` to `2` and `3.5` to `4`.
+    HalfEven,
+    /// Ties round toward zero (half-down), `1.5` to `1` and `-1.5` to `-1`.
+    HalfTowardZero,
+    /// Ties round toward positive infinity (half-up), `1.5` to `2` and `-1.5` to `-1`.
+    HalfTowardPositive,
+    /// Always round toward positive infinity.
+    Ceiling,
+    /// Always round toward negative infinity.
+    Floor,
+    /// Always round toward zero.
+    Truncate,
+}
+
+impl RoundingMode {
+    /// It decides whether a truncated magnitude should be moved one unit away from zero.
+    ///
+    /// Arguments:
+    ///
+    /// * `magnitude`: The absolute value of the result, truncated toward zero.
+    /// * `negative`: Whether the exact result is negative.
+    /// * `half`: How the dropped remainder compares to one half of a unit.
+    /// * `inexact`: Whether the dropped remainder is not zero.
+    ///
+    /// Returns:
+    ///
+    /// true when the magnitude has to be incremented.
+    pub(crate) fn round_away(
+        self,
+        magnitude: i128,
+        negative: bool,
+        half: Ordering,
+        inexact: bool,
+    ) -> bool {
+        if !inexact {
+            return false;
+        }
+
+        match self {
+            Self::HalfAwayFromZero => half != Ordering::Less,
+            Self::HalfEven => {
+                half == Ordering::Greater || (half == Ordering::Equal && magnitude % 2 != 0)
+            }
+            Self::HalfTowardZero => half == Ordering::Greater,
+            Self::HalfTowardPositive => {
+                half == Ordering::Greater || (half == Ordering::Equal && !negative)
+            }
+            Self::Ceiling => !negative,
+            Self::Floor => negative,
+            Self::Truncate => false,
+        }
+    }
+
+    /// It rounds a float to an integer.
+    ///
+    /// Arguments:
+    ///
+    /// * `v`: The value to round.
+    ///
+    /// Returns:
+    ///
+    /// The rounded value.
+    pub(crate) fn round_f64(
+        self,
+        v: f64,
+    ) -> f64 {
+        let trunc = v.trunc();
+        let half = (v - trunc).abs().total_cmp(&0.5);
+        let magnitude = trunc.abs();
+
+        let away = self.round_away((magnitude % 2.) as i128, v < 0., half, v != trunc);
+
+        if away {
+            trunc + v.signum()
+        } else {
+            trunc
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Serialization of currencies, behind the `serde` feature.
+//!
+//! `Currency` serializes as an `{"amount": "12.30", "currency": "USD"}` object by default, the
+//! amount being an exact decimal string. The `decimal`, `minor_units` and `object` modules select
+//! a representation for a field with `#[serde(with = "...")]`. The module is not named `serde`, so
+//! `use currency_rs::*` does not shadow the `serde` crate.
+//!
+//! ```
+//! use currency_rs::*;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct LineItem {
+//!     #[serde(with = "currency_rs::currency_ser::decimal")]
+//!     price: Currency,
+//!     #[serde(with = "currency_rs::currency_ser::minor_units")]
+//!     discount: Currency,
+//! }
+//! ```
+
+use crate::IoError;
+use crate::{currency_math, Backend, Currency, CurrencyErr, CurrencyOpts, DecimalErr, Grouping};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::sync::Arc;
+
+impl<B: Backend> Serialize for Currency<B> {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        object::serialize(self, serializer)
+    }
+}
+
+impl<'de, B: Backend> Deserialize<'de> for Currency<B> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        object::deserialize(deserializer)
+    }
+}
+
+/// The amount as an exact decimal string, e.g. `"-1234.50"`.
+///
+/// The options are not serialized, a deserialized currency has the default options with a
+/// precision of the number of decimal places of the string, or 2 when it has fewer.
+pub mod decimal {
+    use super::*;
+
+    pub fn serialize<B: Backend, S: Serializer>(
+        cur: &Currency<B>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_decimal(cur))
+    }
+
+    pub fn deserialize<'de, B: Backend, D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Currency<B>, D::Error> {
+        let amount = String::deserialize(deserializer)?;
+
+        from_decimal(&amount, CurrencyOpts::new()).map_err(de_error)
+    }
+}
+
+/// The amount as an integer number of minor units with its currency code, e.g.
+/// `{"amount": 1234, "currency": "JPY"}` for `¥1,234`.
+///
+/// The code is left out when the currency has none, and the number of decimal places of the
+/// minor units is written as `scale` when the code does not imply it, so that a deserialized
+/// currency has the same value. It has the options of its ISO 4217 code, or the default options
+/// with its code, if any.
+pub mod minor_units {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct MinorUnits {
+        #[serde(serialize_with = "serialize_int")]
+        amount: i128,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        currency: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        scale: Option<u32>,
+    }
+
+    pub fn serialize<B: Backend, S: Serializer>(
+        cur: &Currency<B>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let precision = cur.opts.precision();
+
+        MinorUnits {
+            amount: cur.minor_units(),
+            currency: cur.code().map(str::to_string),
+            scale: Some(precision).filter(|p| *p != opts_for(cur.code()).precision()),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, B: Backend, D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Currency<B>, D::Error> {
+        let MinorUnits {
+            amount,
+            currency,
+            scale,
+        } = MinorUnits::deserialize(deserializer)?;
+
+        let opts = opts_for(currency.as_deref());
+        let opts = match scale {
+            Some(scale) => opts.set_precision(scale),
+            None => opts,
+        };
+
+        B::try_from_minor(amount, opts.precision())
+            .map(|value| Currency::new(value, opts))
+            .map_err(D::Error::custom)
+    }
+
+    /// It writes an integer as an i64 when it fits, for the formats without 128-bit integers.
+    fn serialize_int<S: Serializer>(
+        value: &i128,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match i64::try_from(*value) {
+            Ok(value) => serializer.serialize_i64(value),
+            Err(_) => serializer.serialize_i128(*value),
+        }
+    }
+}
+
+/// The amount as an exact decimal string with its currency code, e.g.
+/// `{"amount": "1234.50", "currency": "EUR"}`.
+///
+/// The code is left out when the currency has none. A deserialized currency has the options of
+/// its ISO 4217 code, or the default options with its code, if any, and a precision of the
+/// number of decimal places of the amount when it has more.
+pub mod object {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct Object {
+        amount: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        currency: Option<String>,
+    }
+
+    pub fn serialize<B: Backend, S: Serializer>(
+        cur: &Currency<B>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Object {
+            amount: to_decimal(cur),
+            currency: cur.code().map(str::to_string),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, B: Backend, D: Deserializer<'de>>(
+        deserializer: D
+    ) -> Result<Currency<B>, D::Error> {
+        let Object { amount, currency } = Object::deserialize(deserializer)?;
+
+        from_decimal(&amount, opts_for(currency.as_deref())).map_err(de_error)
+    }
+}
+
+/// It returns the options of an ISO 4217 code, or the default options with the code, if any.
+fn opts_for(code: Option<&str>) -> CurrencyOpts {
+    match code {
+        Some(code) => {
+            CurrencyOpts::for_code(code).unwrap_or_else(|| CurrencyOpts::new().set_code(code))
+        }
+        None => CurrencyOpts::new(),
+    }
+}
+
+/// It writes the exact amount, ignoring the increment of the options.
+fn to_decimal<B: Backend>(cur: &Currency<B>) -> String {
+    currency_math::to_decimal_string(cur.minor_units(), cur.opts.precision())
+}
+
+/// It reads a plain decimal string exactly, raising the precision of the options to the number
+/// of decimal places of the string when it has more.
+fn from_decimal<B: Backend>(
+    amount: &str,
+    opts: CurrencyOpts,
+) -> Result<Currency<B>, CurrencyErr> {
+    let places = amount
+        .trim()
+        .split_once('.')
+        .map_or(0, |(_, frac)| frac.len()) as u32;
+
+    let opts = if places > opts.precision() {
+        opts.set_precision(places)
+    } else {
+        opts
+    };
+
+    let minor = currency_math::parse_decimal(amount, opts.precision(), opts.rounding_mode())
+        .map_err(|e| match e {
+            DecimalErr::Invalid => CurrencyErr::invalid(format!("invalid amount {amount:?}")),
+            DecimalErr::Overflow { .. } => CurrencyErr::Overflow,
+        })?;
+
+    let value = B::try_from_minor(minor, opts.precision())?;
+
+    Ok(Currency::new(value, opts))
+}
+
+/// It turns an error into a deserialization error, with the message of the `ParseError` for a
+/// parse error since serde errors have no source.
+fn de_error<E: serde::de::Error>(err: CurrencyErr) -> E {
+    match err {
+        CurrencyErr::ParseErr(e) => E::custom(e),
+        err => E::custom(err),
+    }
+}
+
+/// A grouping is read through `Grouping::new`, so that a zero secondary size or minimum
+/// means the default.
+impl<'de> Deserialize<'de> for Grouping {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            primary: u8,
+            #[serde(default)]
+            secondary: u8,
+            #[serde(default)]
+            min_digits: u8,
+        }
+
+        let raw = Raw::deserialize(deserializer)?;
+
+        Ok(Grouping::new(raw.primary, raw.secondary, raw.min_digits))
+    }
+}
+
+/// An io error is written as its message, and read back as an error of kind `Other`.
+impl Serialize for IoError {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self.inner())
+    }
+}
+
+impl<'de> Deserialize<'de> for IoError {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let message = String::deserialize(deserializer)?;
+
+        Ok(IoError(Arc::new(std::io::Error::other(message))))
+    }
+}
@@ -0,0 +1,706 @@
+use crate::{
+    currency_math, Backend, Currency, CurrencyErr, CurrencyOpts, Decimal, Percentage, RoundingMode,
+};
+use std::borrow::Borrow;
+
+/// The number of decimal places kept for the factor between a net and a gross amount when
+/// compound rates make it longer.
+const FACTOR_SCALE: u32 = 18;
+
+/// A tax rate, e.g. a VAT rate.
+///
+/// A plain rate is charged on the net amount, while a compound rate is charged on the net amount
+/// plus the taxes of the rates before it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TaxRate {
+    name: String,
+    rate: Percentage,
+    compound: bool,
+}
+
+impl TaxRate {
+    /// It creates a tax rate charged on the net amount.
+    ///
+    /// Arguments:
+    ///
+    /// * `name`: The name of the tax, e.g. `"VAT"`.
+    /// * `rate`: A `Percentage` or `BasisPoints`.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the TaxRate struct.
+    pub fn new(
+        name: &str,
+        rate: impl Into<Percentage>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            rate: rate.into(),
+            compound: false,
+        }
+    }
+
+    /// It creates a tax rate charged on the net amount plus the taxes of the rates before it.
+    ///
+    /// Arguments:
+    ///
+    /// * `name`: The name of the tax, e.g. `"QST"`.
+    /// * `rate`: A `Percentage` or `BasisPoints`.
+    ///
+    /// Returns:
+    ///
+    /// A new instance of the TaxRate struct.
+    pub fn compound(
+        name: &str,
+        rate: impl Into<Percentage>,
+    ) -> Self {
+        Self {
+            compound: true,
+            ..Self::new(name, rate)
+        }
+    }
+}
+
+// GETTERS
+impl TaxRate {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn rate(&self) -> Percentage {
+        self.rate
+    }
+
+    pub fn is_compound(&self) -> bool {
+        self.compound
+    }
+}
+
+/// When the taxes of an invoice are rounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TaxRounding {
+    /// The taxes of every line are rounded, and the totals are the sums of the lines.
+    #[default]
+    PerLine,
+    /// The taxes are rounded once on the total, and every line gets the change of the rounded
+    /// running total, so the lines add up to the total and each one is at most a minor unit off.
+    PerInvoice,
+}
+
+/// The taxes charged on amounts, in the order they are added.
+///
+/// All amounts are kept in minor units and every tax is rounded once with the rounding mode of
+/// the options of the amount, so that the net amount and the taxes always add up to the gross
+/// amount.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaxCalculator {
+    rates: Vec<TaxRate>,
+    rounding: TaxRounding,
+    inclusive: bool,
+}
+
+impl TaxCalculator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// > Computes the taxes on a net amount
+    ///
+    /// Arguments:
+    ///
+    /// * `net`: The amount before taxes.
+    ///
+    /// Returns:
+    ///
+    /// The net amount, the taxes and the gross amount, or `CurrencyErr::Overflow`.
+    pub fn add_tax<B: Backend>(
+        &self,
+        net: &Currency<B>,
+    ) -> Result<TaxBreakdown<B>, CurrencyErr> {
+        let mode = net.opts.rounding_mode();
+
+        let taxes = self.taxes_on(net.minor_units(), mode)?;
+
+        self.breakdown(net, net.minor_units(), &taxes)
+    }
+
+    /// > Extracts the net amount and the taxes from a gross amount
+    ///
+    /// The net amount is rounded once, the taxes are computed on it and the last one takes the
+    /// minor unit left over, if any, so that the parts add up to the gross amount.
+    ///
+    /// Arguments:
+    ///
+    /// * `gross`: The amount including taxes.
+    ///
+    /// Returns:
+    ///
+    /// The net amount, the taxes and the gross amount, `CurrencyErr::DivisionByZero` when the
+    /// rates add up to -100%, or `CurrencyErr::Overflow`.
+    pub fn extract_tax<B: Backend>(
+        &self,
+        gross: &Currency<B>,
+    ) -> Result<TaxBreakdown<B>, CurrencyErr> {
+        let mode = gross.opts.rounding_mode();
+
+        let (net, taxes) = self.taxes_in(gross.minor_units(), mode)?;
+
+        self.breakdown(gross, net, &taxes)
+    }
+
+    /// > Computes the taxes of the lines of an invoice
+    ///
+    /// The lines are net amounts, or gross amounts when the calculator is inclusive, and are
+    /// rounded as set by `set_rounding`. The lines and the total always reconcile: the parts of
+    /// the lines add up to the parts of the total.
+    ///
+    /// Arguments:
+    ///
+    /// * `lines`: The amounts of the lines, all of the same currency code.
+    ///
+    /// Returns:
+    ///
+    /// The breakdown of every line and of the total, `CurrencyErr::CurrencyMismatch` when the
+    /// lines have different codes, `CurrencyErr::DivisionByZero` when the rates add up to -100%,
+    /// or `CurrencyErr::Overflow`.
+    pub fn invoice<B: Backend, C: Borrow<Currency<B>>>(
+        &self,
+        lines: impl IntoIterator<Item = C>,
+    ) -> Result<TaxInvoice<B>, CurrencyErr> {
+        let mut lines = lines.into_iter().peekable();
+
+        let like = match lines.peek() {
+            Some(first) => first.borrow().clone(),
+            None => {
+                let opts = CurrencyOpts::new();
+
+                Currency::new(B::from_minor(0, opts.precision()), opts)
+            }
+        };
+
+        let mode = like.opts.rounding_mode();
+
+        let mut breakdowns = vec![];
+        let mut cumulative = 0_i128;
+        let mut running = (0_i128, vec![0_i128; self.rates.len()]);
+
+        for line in lines {
+            let line = line.borrow();
+
+            like.check_code(line)?;
+
+            let amount = currency_math::rescale(
+                line.minor_units(),
+                line.precision(),
+                like.precision(),
+                mode,
+            )
+            .ok_or(CurrencyErr::Overflow)?;
+
+            let (net, taxes) = match self.rounding {
+                TaxRounding::PerLine => self.split(amount, mode)?,
+                TaxRounding::PerInvoice => {
+                    cumulative = cumulative
+                        .checked_add(amount)
+                        .ok_or(CurrencyErr::Overflow)?;
+
+                    let next = self.split(cumulative, mode)?;
+
+                    let net = next.0.checked_sub(running.0);
+                    let taxes = next
+                        .1
+                        .iter()
+                        .zip(&running.1)
+                        .map(|(t, r)| t.checked_sub(*r));
+
+                    let change = net
+                        .zip(taxes.collect::<Option<Vec<_>>>())
+                        .ok_or(CurrencyErr::Overflow)?;
+
+                    running = next;
+                    change
+                }
+            };
+
+            breakdowns.push(self.breakdown(&like, net, &taxes)?);
+        }
+
+        let (net, taxes) = match self.rounding {
+            TaxRounding::PerLine => {
+                let mut sums = (0_i128, vec![0_i128; self.rates.len()]);
+
+                for b in &breakdowns {
+                    sums.0 = sums
+                        .0
+                        .checked_add(b.net.minor_units())
+                        .ok_or(CurrencyErr::Overflow)?;
+
+                    for (sum, (_, tax)) in sums.1.iter_mut().zip(&b.taxes) {
+                        *sum = sum
+                            .checked_add(tax.minor_units())
+                            .ok_or(CurrencyErr::Overflow)?;
+                    }
+                }
+
+                sums
+            }
+            TaxRounding::PerInvoice => running,
+        };
+
+        Ok(TaxInvoice {
+            total: self.breakdown(&like, net, &taxes)?,
+            lines: breakdowns,
+        })
+    }
+
+    /// It splits an amount into its net amount and taxes, as a net or a gross amount depending
+    /// on the calculator.
+    fn split(
+        &self,
+        amount: i128,
+        mode: RoundingMode,
+    ) -> Result<(i128, Vec<i128>), CurrencyErr> {
+        if self.inclusive {
+            self.taxes_in(amount, mode)
+        } else {
+            Ok((amount, self.taxes_on(amount, mode)?))
+        }
+    }
+
+    /// It computes the taxes on a net number of minor units, each rounded once.
+    fn taxes_on(
+        &self,
+        net: i128,
+        mode: RoundingMode,
+    ) -> Result<Vec<i128>, CurrencyErr> {
+        let mut taxes = Vec::with_capacity(self.rates.len());
+        let mut taxed = net;
+
+        for rate in &self.rates {
+            let ratio = rate.rate.ratio();
+
+            let base = if rate.compound { taxed } else { net };
+
+            let tax = currency_math::pow10(ratio.scale())
+                .and_then(|d| currency_math::mul_div_round(base, ratio.mantissa(), d, mode))
+                .ok_or(CurrencyErr::Overflow)?;
+
+            taxed = taxed.checked_add(tax).ok_or(CurrencyErr::Overflow)?;
+            taxes.push(tax);
+        }
+
+        Ok(taxes)
+    }
+
+    /// It extracts the net number of minor units and the taxes from a gross one, the last tax
+    /// taking the minor unit left over by the rounding.
+    fn taxes_in(
+        &self,
+        gross: i128,
+        mode: RoundingMode,
+    ) -> Result<(i128, Vec<i128>), CurrencyErr> {
+        let (factor, scale) = self.factor().ok_or(CurrencyErr::Overflow)?;
+
+        if factor == 0 {
+            return Err(CurrencyErr::DivisionByZero);
+        }
+
+        let net = currency_math::pow10(scale)
+            .and_then(|one| currency_math::mul_div_round(gross, one, factor, mode))
+            .ok_or(CurrencyErr::Overflow)?;
+
+        let mut taxes = self.taxes_on(net, mode)?;
+
+        let left = taxes
+            .iter()
+            .try_fold(0_i128, |sum, tax| sum.checked_add(*tax))
+            .and_then(|tax| gross.checked_sub(net)?.checked_sub(tax))
+            .ok_or(CurrencyErr::Overflow)?;
+
+        match taxes.last_mut() {
+            Some(last) => *last = last.checked_add(left).ok_or(CurrencyErr::Overflow)?,
+            None if left != 0 => return Err(CurrencyErr::Overflow),
+            None => {}
+        }
+
+        Ok((net, taxes))
+    }
+
+    /// It computes the gross amount of a net amount of one as a decimal, `1.2` for a single
+    /// rate of 20%.
+    fn factor(&self) -> Option<(i128, u32)> {
+        let mut factor = (1_i128, 0_u32);
+        let mut taxed = (1_i128, 0_u32);
+
+        for rate in &self.rates {
+            let ratio = rate.rate.ratio();
+
+            let base = if rate.compound { taxed } else { (1, 0) };
+
+            let scale = (base.1 + ratio.scale()).min(FACTOR_SCALE);
+
+            let tax = currency_math::mul_decimal(
+                base.0,
+                base.1,
+                ratio.mantissa(),
+                ratio.scale(),
+                scale,
+                RoundingMode::default(),
+            )?;
+
+            factor = add_decimal(factor, (tax, scale))?;
+            taxed = add_decimal(taxed, (tax, scale))?;
+        }
+
+        Some(factor)
+    }
+
+    /// It creates the breakdown of a net amount and its taxes in the options of a currency.
+    fn breakdown<B: Backend>(
+        &self,
+        like: &Currency<B>,
+        net: i128,
+        taxes: &[i128],
+    ) -> Result<TaxBreakdown<B>, CurrencyErr> {
+        let tax = taxes
+            .iter()
+            .try_fold(0_i128, |sum, t| sum.checked_add(*t))
+            .ok_or(CurrencyErr::Overflow)?;
+
+        let gross = net.checked_add(tax).ok_or(CurrencyErr::Overflow)?;
+
+        Ok(TaxBreakdown {
+            net: like.with_minor(net)?,
+            taxes: self
+                .rates
+                .iter()
+                .zip(taxes)
+                .map(|(rate, t)| Ok((rate.name.clone(), like.with_minor(*t)?)))
+                .collect::<Result<_, CurrencyErr>>()?,
+            tax: like.with_minor(tax)?,
+            gross: like.with_minor(gross)?,
+        })
+    }
+}
+
+// SETTERS
+impl TaxCalculator {
+    /// It adds a rate after the ones already added, which matters for compound rates.
+    pub fn add_rate(
+        mut self,
+        rate: TaxRate,
+    ) -> Self {
+        self.rates.push(rate);
+        self
+    }
+
+    pub fn set_rounding(
+        mut self,
+        rounding: TaxRounding,
+    ) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Whether the lines of `invoice` are gross amounts that already include the taxes.
+    pub fn set_inclusive(
+        mut self,
+        inclusive: bool,
+    ) -> Self {
+        self.inclusive = inclusive;
+        self
+    }
+}
+
+// GETTERS
+impl TaxCalculator {
+    pub fn rates(&self) -> &[TaxRate] {
+        &self.rates
+    }
+
+    pub fn rounding(&self) -> TaxRounding {
+        self.rounding
+    }
+
+    pub fn inclusive(&self) -> bool {
+        self.inclusive
+    }
+}
+
+/// A net amount, the taxes charged on it and the gross amount, which always add up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxBreakdown<B: Backend = i128> {
+    net: Currency<B>,
+    taxes: Vec<(String, Currency<B>)>,
+    tax: Currency<B>,
+    gross: Currency<B>,
+}
+
+// GETTERS
+impl<B: Backend> TaxBreakdown<B> {
+    pub fn net(&self) -> &Currency<B> {
+        &self.net
+    }
+
+    /// The tax of every rate with its name, in the order of the rates.
+    pub fn taxes(&self) -> &[(String, Currency<B>)] {
+        &self.taxes
+    }
+
+    /// The sum of the taxes.
+    pub fn tax(&self) -> &Currency<B> {
+        &self.tax
+    }
+
+    pub fn gross(&self) -> &Currency<B> {
+        &self.gross
+    }
+}
+
+/// The breakdowns of the lines of an invoice and of its total.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxInvoice<B: Backend = i128> {
+    lines: Vec<TaxBreakdown<B>>,
+    total: TaxBreakdown<B>,
+}
+
+// GETTERS
+impl<B: Backend> TaxInvoice<B> {
+    pub fn lines(&self) -> &[TaxBreakdown<B>] {
+        &self.lines
+    }
+
+    pub fn total(&self) -> &TaxBreakdown<B> {
+        &self.total
+    }
+}
+
+/// A bracket of a progressive tax schedule: the income from its threshold up to the threshold
+/// of the next bracket is taxed at its marginal rate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxBracket<B: Backend = i128> {
+    threshold: Currency<B>,
+    rate: Percentage,
+}
+
+// GETTERS
+impl<B: Backend> TaxBracket<B> {
+    /// The income the bracket starts at.
+    pub fn threshold(&self) -> &Currency<B> {
+        &self.threshold
+    }
+
+    pub fn rate(&self) -> Percentage {
+        self.rate
+    }
+}
+
+/// A progressive tax schedule, brackets of marginal rates ordered by threshold.
+///
+/// Income below the first threshold is not taxed and the last bracket has no upper bound.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaxSchedule<B: Backend = i128> {
+    brackets: Vec<TaxBracket<B>>,
+}
+
+impl<B: Backend> Default for TaxSchedule<B> {
+    fn default() -> Self {
+        Self { brackets: vec![] }
+    }
+}
+
+impl<B: Backend> TaxSchedule<B> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// It adds a bracket above the ones already added.
+    ///
+    /// Arguments:
+    ///
+    /// * `threshold`: The income the bracket starts at.
+    /// * `rate`: The marginal rate of the bracket, a `Percentage` or `BasisPoints`.
+    ///
+    /// Returns:
+    ///
+    /// The schedule with the bracket added, `CurrencyErr::CurrencyMismatch` when the threshold
+    /// has another code than the ones before it, or `CurrencyErr::InvalidSchedule` when it is not
+    /// above the threshold of the last bracket.
+    pub fn add_bracket(
+        mut self,
+        threshold: Currency<B>,
+        rate: impl Into<Percentage>,
+    ) -> Result<Self, CurrencyErr> {
+        if let Some(last) = self.brackets.last() {
+            last.threshold.check_code(&threshold)?;
+
+            if exact(&threshold) <= exact(&last.threshold) {
+                return Err(CurrencyErr::InvalidSchedule(format!(
+                    "threshold {} is not above {}",
+                    exact(&threshold),
+                    exact(&last.threshold)
+                )));
+            }
+        }
+
+        self.brackets.push(TaxBracket {
+            threshold,
+            rate: rate.into(),
+        });
+
+        Ok(self)
+    }
+
+    /// > Computes the tax on an income and the part of it owed in every bracket
+    ///
+    /// The tax is rounded once with the rounding mode of the income, and every bracket gets the
+    /// change of the rounded running total, so the taxes of the brackets always add up to the
+    /// total and each one is at most a minor unit off its exact value.
+    ///
+    /// Arguments:
+    ///
+    /// * `income`: The income to tax, a negative income owes nothing.
+    ///
+    /// Returns:
+    ///
+    /// The tax in the options of the income, `CurrencyErr::CurrencyMismatch` when the income has
+    /// another code than the thresholds, or `CurrencyErr::Overflow`.
+    pub fn tax(
+        &self,
+        income: &Currency<B>,
+    ) -> Result<ScheduleTax<B>, CurrencyErr> {
+        let mode = income.opts.rounding_mode();
+
+        let thresholds = self
+            .brackets
+            .iter()
+            .map(|b| {
+                income.check_code(&b.threshold)?;
+
+                currency_math::rescale(
+                    b.threshold.minor_units(),
+                    b.threshold.precision(),
+                    income.precision(),
+                    mode,
+                )
+                .ok_or(CurrencyErr::Overflow)
+            })
+            .collect::<Result<Vec<_>, CurrencyErr>>()?;
+
+        let scale = self
+            .brackets
+            .iter()
+            .map(|b| b.rate.ratio().scale())
+            .max()
+            .unwrap_or(0);
+
+        let one = currency_math::pow10(scale).ok_or(CurrencyErr::Overflow)?;
+
+        let mut exact_tax = 0_i128;
+        let mut tax = 0_i128;
+        let mut brackets = Vec::with_capacity(self.brackets.len());
+
+        for (i, bracket) in self.brackets.iter().enumerate() {
+            let upper = match thresholds.get(i + 1) {
+                Some(next) => income.minor_units().min(*next),
+                None => income.minor_units(),
+            };
+
+            let taxable = upper
+                .checked_sub(thresholds[i])
+                .ok_or(CurrencyErr::Overflow)?
+                .max(0);
+
+            let ratio = bracket.rate.ratio();
+
+            exact_tax = currency_math::pow10(scale - ratio.scale())
+                .and_then(|p| ratio.mantissa().checked_mul(p))
+                .and_then(|m| taxable.checked_mul(m))
+                .and_then(|t| exact_tax.checked_add(t))
+                .ok_or(CurrencyErr::Overflow)?;
+
+            let next = currency_math::div_round(exact_tax, one, mode);
+
+            brackets.push(BracketTax {
+                bracket: bracket.clone(),
+                taxable: income.with_minor(taxable)?,
+                tax: income.with_minor(next - tax)?,
+            });
+
+            tax = next;
+        }
+
+        Ok(ScheduleTax {
+            brackets,
+            tax: income.with_minor(tax)?,
+        })
+    }
+}
+
+// GETTERS
+impl<B: Backend> TaxSchedule<B> {
+    pub fn brackets(&self) -> &[TaxBracket<B>] {
+        &self.brackets
+    }
+}
+
+/// The part of an income that falls in a bracket and the tax owed on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BracketTax<B: Backend = i128> {
+    bracket: TaxBracket<B>,
+    taxable: Currency<B>,
+    tax: Currency<B>,
+}
+
+// GETTERS
+impl<B: Backend> BracketTax<B> {
+    pub fn bracket(&self) -> &TaxBracket<B> {
+        &self.bracket
+    }
+
+    /// The part of the income taxed at the rate of the bracket.
+    pub fn taxable(&self) -> &Currency<B> {
+        &self.taxable
+    }
+
+    pub fn tax(&self) -> &Currency<B> {
+        &self.tax
+    }
+}
+
+/// The tax on an income with its breakdown by bracket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleTax<B: Backend = i128> {
+    brackets: Vec<BracketTax<B>>,
+    tax: Currency<B>,
+}
+
+// GETTERS
+impl<B: Backend> ScheduleTax<B> {
+    /// Every bracket of the schedule, in order, including the ones the income does not reach.
+    pub fn brackets(&self) -> &[BracketTax<B>] {
+        &self.brackets
+    }
+
+    pub fn tax(&self) -> &Currency<B> {
+        &self.tax
+    }
+}
+
+/// It returns the exact value of a currency as a decimal.
+fn exact<B: Backend>(cur: &Currency<B>) -> Decimal {
+    Decimal::new(cur.minor_units(), cur.precision())
+}
+
+/// It adds two decimals given as their digits and number of decimal places.
+fn add_decimal(
+    a: (i128, u32),
+    b: (i128, u32),
+) -> Option<(i128, u32)> {
+    let scale = a.1.max(b.1);
+
+    let a = currency_math::rescale(a.0, a.1, scale, RoundingMode::default())?;
+    let b = currency_math::rescale(b.0, b.1, scale, RoundingMode::default())?;
+
+    Some((a.checked_add(b)?, scale))
+}
@@ -1,6 +1,25 @@
+use crate::BasisPoints;
 use crate::Currency;
 use crate::CurrencyErr;
 use crate::CurrencyOpts;
+use crate::DatedRateTable;
+use crate::Decimal;
+use crate::ExchangeRate;
+use crate::Grouping;
+use crate::IsoCurrency;
+use crate::Locale;
+use crate::MoneyBag;
+use crate::ParseErrKind;
+use crate::ParseError;
+use crate::Percentage;
+use crate::RateDate;
+use crate::RateProvider;
+use crate::RateTable;
+use crate::RoundingMode;
+use crate::TaxCalculator;
+use crate::TaxRate;
+use crate::TaxRounding;
+use crate::TaxSchedule;
 
 #[test]
 fn should_allow_numbers() {
@@ -305,6 +324,24 @@ fn should_create_non_equal_distribution_with_a_negative_penny() {
     assert_eq!(total, -0.01, "sum of values matches our original amount");
 }
 
+#[test]
+fn should_distribute_the_smallest_amount() {
+    let min = Currency::new_string("-1701411834604692317316873037158841057.28", None).unwrap();
+
+    assert_eq!(min.minor_units(), i128::MIN);
+
+    let values = min.distribute(3);
+
+    assert_eq!(values[0].minor_units(), i128::MIN / 3 - 1);
+    assert_eq!(values[2].minor_units(), i128::MIN / 3);
+    assert_eq!(
+        values
+            .iter()
+            .try_fold(0_i128, |sum, v| sum.checked_add(v.minor_units())),
+        Some(i128::MIN)
+    );
+}
+
 #[test]
 fn should_get_dollar_value() {
     let value = Currency::new_float(1.23, None);
@@ -312,6 +349,11 @@ fn should_get_dollar_value() {
     assert_eq!((value.clone() + 2.).dollars(), 3, "is dollar amount");
     assert_eq!((value.clone() + 0.8).dollars(), 2, "is dollar amount");
     assert_eq!((value - 3.).dollars(), -1, "is negative dollar amount");
+
+    let big = |s| Currency::new_string(s, None).unwrap();
+
+    assert_eq!(big("1000000000000000000000000000000").dollars(), i64::MAX);
+    assert_eq!(big("-1000000000000000000000000000000").dollars(), i64::MIN);
 }
 
 #[test]
@@ -784,21 +826,21 @@ fn should_allow_arbitrary_rounding_increments() {
 
 #[test]
 fn should_handle_max_safe_integer() {
-    let max1 = Currency::new_float(i128::MAX as f64, None);
-    let max2 = Currency::new_float(-i128::MAX as f64, None);
+    let max1 = Currency::new_string("1701411834604692317316873037158841057.27", None).unwrap();
+    let max2 = Currency::new_string("-1701411834604692317316873037158841057.27", None).unwrap();
     let max3 = Currency::new_float(i64::MAX as f64, None);
     let max4 = Currency::new_float(-i64::MAX as f64, None);
     let max5 = Currency::new_float(i32::MAX as f64, None);
     let max6 = Currency::new_float(-i32::MAX as f64, None);
 
     assert_eq!(
-        max1.value(),
-        i128::MAX as f64,
+        max1.minor_units(),
+        i128::MAX,
         "currency does not handle i128 correctly"
     );
     assert_eq!(
-        max2.value(),
-        -i128::MAX as f64,
+        max2.minor_units(),
+        -i128::MAX,
         "currency does not handle -i128 correctly"
     );
     assert_eq!(
@@ -823,6 +865,22 @@ fn should_handle_max_safe_integer() {
     );
 }
 
+#[test]
+fn should_keep_exact_cents_above_max_safe_integer() {
+    let c1 = Currency::new_string("90071992547409.93", None).unwrap();
+    let c2 = Currency::new_string("92233720368547758.07", None).unwrap();
+
+    assert_eq!(c1.minor_units(), 9007199254740993);
+    assert_eq!((c1.clone() + c1.clone()).minor_units(), 18014398509481986);
+    assert_eq!(c2.minor_units(), i64::MAX as i128);
+    assert_eq!(c2.format(), "$92,233,720,368,547,758.07");
+    assert_eq!(c2.to_string(), "92233720368547758.07");
+
+    let total: i128 = c2.distribute(3).iter().map(|c| c.minor_units()).sum();
+
+    assert_eq!(total, i64::MAX as i128, "distribution does not lose cents");
+}
+
 #[test]
 fn should_allow_creation_from_cents() {
     let opts = Some(CurrencyOpts::new().set_precision(2).set_from_cents(true));
@@ -920,7 +978,34 @@ fn should_handle_subtract_with_from_cents_option() {
     let c2 = Currency::new_float(12345., opts);
 
     assert_eq!((c1.clone() - 123.).value(), 122.22);
-    assert_eq!((c1 - c2).value(), 122.22);
+    assert_eq!((c1 - c2).value(), 0.);
+}
+
+#[test]
+fn should_add_and_subtract_exact_amounts_with_operators_and_from_cents() {
+    let opts = Some(CurrencyOpts::new().set_from_cents(true));
+
+    let a = Currency::new_float(150., opts.clone());
+    let b = Currency::new_float(150., opts);
+
+    assert_eq!((a.clone() + b.clone()).value(), 3.);
+    assert_eq!((&a + &b).value(), 3.);
+    assert_eq!((a.clone() - b.clone()).value(), 0.);
+    assert_eq!((&a - &b).value(), 0.);
+
+    let mut total = a.clone();
+
+    total += &b;
+    total += b.clone();
+
+    assert_eq!(total.value(), 4.5);
+    assert_eq!(total.format(), "$4.50");
+
+    total -= &a;
+    total -= a.clone();
+
+    assert_eq!(total.value(), 1.5);
+    assert_eq!(total, a.clone().try_add(b.clone()).unwrap() - &a);
 }
 
 #[test]
@@ -994,3 +1079,2072 @@ fn should_format_vedic_groupings() {
         "value is not $12,34,567.8912"
     );
 }
+
+#[test]
+fn should_support_i64_backend() {
+    let c1 = Currency::<i64>::from_float(2.51, None) + 0.01;
+    let c2 = Currency::<i64>::from_string("92233720368547758.07", None).unwrap() + 1.;
+
+    assert_eq!(c1.value(), 2.52);
+    assert_eq!(c1.amount(), 252_i64);
+    assert_eq!(c2.amount(), i64::MAX, "i64 backend does not saturate");
+}
+
+#[test]
+fn should_support_legacy_f64_backend() {
+    let c1 = Currency::<f64>::from_float(17.955, None);
+    let c2 = Currency::<f64>::from_float(9.87, None) / 2.;
+
+    assert_eq!(c1.value(), 17.96);
+    assert_eq!(c1.amount(), 1796.);
+    assert_eq!(c2.value(), 4.94);
+    assert_eq!(c2.format(), "$4.94");
+}
+
+#[test]
+fn should_support_decimal_backend_with_high_precision() {
+    let opts = Some(CurrencyOpts::new().set_precision(8).set_symbol("BTC "));
+
+    let c1 = Currency::<Decimal>::from_string("0.12345678", opts.clone()).unwrap() * 3.;
+    let c2 = Currency::<Decimal>::from_float(0.1, opts) + 0.2;
+
+    assert_eq!(c1.amount(), Decimal::new(37037034, 8));
+    assert_eq!(c1.format(), "BTC 0.37037034");
+    assert_eq!(c2.amount(), Decimal::new(3, 1));
+    assert_eq!(c2.minor_units(), 30000000);
+}
+
+#[test]
+fn should_convert_between_backends() {
+    let c1 = Currency::<f64>::from_float(1.23, None);
+    let c2 = Currency::<i128>::from_cur(c1, Some(CurrencyOpts::new().set_precision(3)));
+    let c3 = Currency::<Decimal>::from_cur(c2.clone(), None);
+
+    assert_eq!(c2.minor_units(), 1230);
+    assert_eq!(c3.amount(), Decimal::new(123, 2));
+}
+
+#[test]
+fn should_parse_and_compare_decimals() {
+    let d1: Decimal = "1.50".parse().unwrap();
+    let d2 = Decimal::new(15, 1);
+
+    assert_eq!(d1, d2);
+    assert_eq!(d1.to_string(), "1.50");
+    assert_eq!(d2.normalize().mantissa(), 15);
+    assert_eq!(Decimal::from_f64(0.1), Some(Decimal::new(1, 1)));
+    assert!(Decimal::new(-1, 2) < Decimal::new(0, 0));
+    assert!("1.2.3".parse::<Decimal>().is_err());
+}
+
+#[test]
+fn should_create_options_for_iso_codes() {
+    let jpy = Currency::new_float(1234.56, CurrencyOpts::for_code("JPY"));
+    let kwd = Currency::new_float(1.2345, CurrencyOpts::for_code("kwd"));
+    let eur = Currency::new_float(1.5, CurrencyOpts::for_code("EUR"));
+
+    assert_eq!(jpy.format(), "¥1,235");
+    assert_eq!(kwd.format(), "KWD1.235");
+    assert_eq!(kwd.int_value(), 1235.);
+    assert_eq!(eur.format(), "€1.50");
+    assert!(CurrencyOpts::for_code("XYZ").is_none());
+}
+
+#[test]
+fn should_look_up_iso_currencies() {
+    let usd = IsoCurrency::from_code("USD").unwrap();
+
+    assert_eq!(usd.numeric(), 840);
+    assert_eq!(usd.name(), "US Dollar");
+    assert_eq!(usd.minor_units(), 2);
+    assert_eq!(IsoCurrency::from_numeric(414).unwrap().code(), "KWD");
+    assert_eq!(IsoCurrency::from_code("CAD").unwrap().narrow_symbol(), "$");
+    assert!(IsoCurrency::all()
+        .windows(2)
+        .all(|w| w[0].code() < w[1].code()));
+
+    let xdr = IsoCurrency::from_code("XDR").unwrap();
+    assert_eq!(xdr.numeric(), 960);
+    assert_eq!(xdr.minor_units(), 0);
+    assert_eq!(IsoCurrency::from_numeric(959).unwrap().code(), "XAU");
+    for code in [
+        "XAG", "XAU", "XBA", "XBB", "XBC", "XBD", "XDR", "XPD", "XPT", "XSU", "XTS", "XUA", "XXX",
+    ] {
+        assert!(IsoCurrency::from_code(code).is_some(), "{code} is missing");
+    }
+
+    let opts = CurrencyOpts::for_code("XAU").map(|o| o.set_strict(true));
+    assert_eq!(Currency::new_string("XAU 12", opts).unwrap().value(), 12.);
+}
+
+#[test]
+fn should_carry_currency_code() {
+    let usd = Currency::new_float(1.23, CurrencyOpts::for_code("USD"));
+    let eur = Currency::new_float(1.23, Some(CurrencyOpts::new().set_code("eur")));
+    let plain = Currency::new_float(1.23, None);
+
+    assert_eq!(usd.code(), Some("USD"));
+    assert_eq!(eur.code(), Some("EUR"));
+    assert_eq!(plain.code(), None);
+}
+
+#[test]
+fn should_reject_mixed_currency_arithmetic() {
+    let usd = Currency::new_float(1.23, CurrencyOpts::for_code("USD"));
+    let eur = Currency::new_float(1.23, CurrencyOpts::for_code("EUR"));
+    let plain = Currency::new_float(1., None);
+
+    assert_eq!(
+        usd.clone().try_add(eur.clone()).unwrap_err(),
+        CurrencyErr::CurrencyMismatch {
+            expected: "USD".to_string(),
+            found: "EUR".to_string()
+        }
+    );
+    assert!(matches!(
+        eur.try_subtract(usd.clone()),
+        Err(CurrencyErr::CurrencyMismatch { .. })
+    ));
+    assert_eq!(usd.clone().try_add(usd.clone()).unwrap().value(), 2.46);
+    assert_eq!(usd.clone().try_add(plain.clone()).unwrap().value(), 2.23);
+    assert_eq!((plain + usd).value(), 2.23);
+}
+
+#[test]
+fn should_add_exact_minor_units_with_from_cents() {
+    let cents = Some(CurrencyOpts::new().set_from_cents(true));
+    let c = Currency::new_float(150., cents.clone());
+    let milli = Currency::new_float(1.005, Some(CurrencyOpts::new().set_precision(3)));
+
+    assert_eq!(c.value(), 1.5);
+    assert_eq!(c.clone().try_add(c.clone()).unwrap().value(), 3.);
+    assert_eq!(c.clone().try_subtract(c.clone()).unwrap().value(), 0.);
+    assert_eq!(c.clone().try_add(milli).unwrap().value(), 2.51);
+    assert_eq!(
+        Currency::new_float(0., cents)
+            .try_subtract(c)
+            .unwrap()
+            .value(),
+        -1.5
+    );
+}
+
+#[test]
+#[should_panic(expected = "cannot add currencies with different codes")]
+fn should_panic_on_mixed_currency_operator() {
+    let usd = Currency::new_float(1.23, CurrencyOpts::for_code("USD"));
+    let eur = Currency::new_float(1.23, CurrencyOpts::for_code("EUR"));
+
+    let _ = usd + eur;
+}
+
+#[test]
+fn should_round_half_even() {
+    let opts = Some(CurrencyOpts::new().set_rounding_mode(RoundingMode::HalfEven));
+
+    let c = |v| Currency::new_float(v, opts.clone());
+    let cs = |v| Currency::new_string(v, opts.clone()).unwrap();
+
+    assert_eq!(c(17.955).value(), 17.96);
+    assert_eq!(c(17.945).value(), 17.94);
+    assert_eq!(c(-17.945).value(), -17.94);
+    assert_eq!(cs("2.345").value(), 2.34);
+    assert_eq!(cs("2.3451").value(), 2.35);
+    assert_eq!((c(1.25) * 0.5).value(), 0.62);
+    assert_eq!((c(1.25) / 2.).value(), 0.62);
+    assert_eq!((c(1.35) / 2.).value(), 0.68);
+}
+
+#[test]
+fn should_round_with_directed_modes() {
+    let c =
+        |v, mode| Currency::new_float(v, Some(CurrencyOpts::new().set_rounding_mode(mode))).value();
+
+    assert_eq!(c(1.235, RoundingMode::HalfTowardZero), 1.23);
+    assert_eq!(c(-1.235, RoundingMode::HalfTowardZero), -1.23);
+    assert_eq!(c(1.236, RoundingMode::HalfTowardZero), 1.24);
+    assert_eq!(c(1.235, RoundingMode::HalfTowardPositive), 1.24);
+    assert_eq!(c(-1.235, RoundingMode::HalfTowardPositive), -1.23);
+    assert_eq!(c(1.231, RoundingMode::Ceiling), 1.24);
+    assert_eq!(c(-1.239, RoundingMode::Ceiling), -1.23);
+    assert_eq!(c(1.239, RoundingMode::Floor), 1.23);
+    assert_eq!(c(-1.231, RoundingMode::Floor), -1.24);
+    assert_eq!(c(1.239, RoundingMode::Truncate), 1.23);
+    assert_eq!(c(-1.239, RoundingMode::Truncate), -1.23);
+}
+
+#[test]
+fn should_round_increments_with_rounding_mode() {
+    let opts = CurrencyOpts::new().set_increment(0.05);
+
+    let floor = Some(opts.clone().set_rounding_mode(RoundingMode::Floor));
+    let ceiling = Some(opts.set_rounding_mode(RoundingMode::Ceiling));
+
+    assert_eq!(Currency::new_float(1.04, floor).format(), "$1.00");
+    assert_eq!(Currency::new_float(1.01, ceiling).format(), "$1.05");
+
+    let min = Currency::new_string(
+        "-1701411834604692317316873037158841057.28",
+        Some(CurrencyOpts::new().set_increment(0.05)),
+    )
+    .unwrap();
+
+    assert_eq!(min.minor_units(), i128::MIN);
+    assert_eq!(
+        min.format(),
+        "-$1,701,411,834,604,692,317,316,873,037,158,841,057.28"
+    );
+}
+
+#[test]
+fn should_round_legacy_f64_backend_with_rounding_mode() {
+    let opts = Some(CurrencyOpts::new().set_rounding_mode(RoundingMode::HalfEven));
+
+    let c1 = Currency::<f64>::from_float(17.945, opts.clone());
+    let c2 = Currency::<f64>::from_float(1.25, opts) * 0.5;
+
+    assert_eq!(c1.value(), 17.94);
+    assert_eq!(c2.value(), 0.62);
+}
+
+#[test]
+fn should_divide_by_negative_numbers() {
+    let c1 = Currency::new_float(9.87, None) / -2.;
+    let c2 = Currency::new_float(9.87, None) / 0.;
+
+    assert_eq!(c1.value(), -4.94);
+    assert_eq!(c2.value(), 9.87, "dividing by zero is not a no-op");
+}
+
+#[test]
+fn should_report_errors_with_checked_arithmetic() {
+    let c = || Currency::new_float(1.23, None);
+    let max = Currency::new_string("1701411834604692317316873037158841057.27", None).unwrap();
+
+    assert_eq!(c().checked_add(0.01).unwrap().value(), 1.24);
+    assert_eq!(c().checked_sub(0.03).unwrap().value(), 1.2);
+    assert_eq!(c().checked_mul(2.).unwrap().value(), 2.46);
+    assert_eq!(c().checked_div(-3.).unwrap().value(), -0.41);
+    assert_eq!(
+        c().checked_div(0.).unwrap_err(),
+        CurrencyErr::DivisionByZero
+    );
+    assert_eq!(
+        c().checked_add(f64::NAN).unwrap_err(),
+        CurrencyErr::NonFiniteNumber
+    );
+    assert_eq!(
+        c().checked_mul(f64::INFINITY).unwrap_err(),
+        CurrencyErr::NonFiniteNumber
+    );
+    assert_eq!(
+        max.clone().checked_add(0.01).unwrap_err(),
+        CurrencyErr::Overflow
+    );
+    assert_eq!(max.checked_mul(2.).unwrap_err(), CurrencyErr::Overflow);
+    assert_eq!(
+        Currency::<i64>::from_float(1., None)
+            .checked_mul(1e17)
+            .unwrap_err(),
+        CurrencyErr::Overflow
+    );
+    assert_eq!(
+        Currency::<f64>::from_float(1., None)
+            .checked_mul(1e15)
+            .unwrap_err(),
+        CurrencyErr::PrecisionLoss
+    );
+}
+
+#[test]
+fn should_round_tiny_checked_results_and_report_overflow() {
+    let c = |mode| Currency::new_float(1.23, Some(CurrencyOpts::new().set_rounding_mode(mode)));
+    let half_up = || c(RoundingMode::HalfAwayFromZero);
+    let max = Currency::new_string("1701411834604692317316873037158841057.27", None).unwrap();
+
+    assert_eq!(half_up().checked_div(1e300).unwrap().minor_units(), 0);
+    assert_eq!(half_up().checked_div(-1e300).unwrap().minor_units(), 0);
+    assert_eq!(half_up().checked_mul(1e-300).unwrap().minor_units(), 0);
+    assert_eq!(half_up().checked_mul(5e-324).unwrap().minor_units(), 0);
+    assert_eq!(
+        c(RoundingMode::Ceiling)
+            .checked_div(1e300)
+            .unwrap()
+            .minor_units(),
+        1
+    );
+    assert_eq!(
+        c(RoundingMode::Floor)
+            .checked_mul(-1e-300)
+            .unwrap()
+            .minor_units(),
+        -1
+    );
+    assert_eq!(
+        half_up().checked_div(1e-300).unwrap_err(),
+        CurrencyErr::Overflow
+    );
+    assert_eq!(
+        half_up().checked_mul(1e300).unwrap_err(),
+        CurrencyErr::Overflow
+    );
+    assert_eq!(
+        half_up().checked_div(f64::MIN_POSITIVE).unwrap_err(),
+        CurrencyErr::Overflow
+    );
+
+    // the quotient is about 0.945 minor units
+    assert_eq!(max.clone().checked_div(1.8e38).unwrap().minor_units(), 1);
+    assert_eq!(max.checked_div(f64::MAX).unwrap().minor_units(), 0);
+    assert_eq!(
+        half_up().checked_div(1e-30).unwrap().minor_units(),
+        123 * 10_i128.pow(30)
+    );
+
+    let big = Currency::new_string("10000000000000000000000000000", None).unwrap();
+    assert_eq!(
+        big.checked_mul(1.2345678901234567e-21)
+            .unwrap()
+            .minor_units(),
+        1234567890
+    );
+}
+
+#[test]
+fn should_report_overflow_when_adding_currencies() {
+    let max = Currency::new_string("1701411834604692317316873037158841057.27", None).unwrap();
+    let cent = Currency::new_float(0.01, None);
+
+    assert_eq!(
+        max.clone().try_add(cent.clone()).unwrap_err(),
+        CurrencyErr::Overflow
+    );
+    assert_eq!((max + cent).minor_units(), i128::MAX);
+}
+
+#[test]
+fn should_saturate_with_saturating_arithmetic() {
+    let c = || Currency::<i64>::from_float(1.23, None);
+
+    assert_eq!(c().saturating_add(0.01).value(), 1.24);
+    assert_eq!(c().saturating_mul(1e17).amount(), i64::MAX);
+    assert_eq!(c().saturating_mul(-1e17).amount(), i64::MIN);
+    assert_eq!(c().saturating_sub(f64::INFINITY).amount(), i64::MIN);
+    assert_eq!(c().saturating_add(f64::NAN).value(), 1.23);
+    assert_eq!(c().saturating_div(0.).amount(), i64::MAX);
+    assert_eq!(c().saturating_div(-3.).value(), -0.41);
+}
+
+#[test]
+fn should_allocate_by_ratios() {
+    let values = |c: Currency, ratios: &[f64]| {
+        c.allocate(ratios)
+            .iter()
+            .map(|x| x.value())
+            .collect::<Vec<f64>>()
+    };
+
+    assert_eq!(
+        values(Currency::new_float(100., None), &[70., 20., 10.]),
+        vec![70., 20., 10.]
+    );
+    assert_eq!(
+        values(Currency::new_float(0.05, None), &[0.7, 0.2, 0.1]),
+        vec![0.04, 0.01, 0.]
+    );
+    assert_eq!(
+        values(Currency::new_float(1., None), &[1., 1., 1.]),
+        vec![0.34, 0.33, 0.33]
+    );
+    assert_eq!(
+        values(Currency::new_float(-1., None), &[1., 1., 1.]),
+        vec![-0.34, -0.33, -0.33]
+    );
+    assert_eq!(
+        values(Currency::new_float(10., None), &[1., 0., 2.]),
+        vec![3.33, 0., 6.67]
+    );
+    assert!(values(Currency::new_float(10., None), &[]).is_empty());
+    assert!(values(Currency::new_float(10., None), &[0., 0.]).is_empty());
+    assert!(values(Currency::new_float(10., None), &[1., -1.]).is_empty());
+    assert!(values(Currency::new_float(10., None), &[1., f64::NAN]).is_empty());
+}
+
+#[test]
+fn should_allocate_to_the_original_value() {
+    let opts = CurrencyOpts::new().set_from_cents(true);
+    let c = Currency::new_float(1001., Some(opts.clone()));
+
+    let parts = c.clone().allocate(&[0.5, 0.25, 0.125, 0.125]);
+    let cents = parts.iter().map(|x| x.minor_units()).collect::<Vec<i128>>();
+
+    assert_eq!(cents, vec![501, 250, 125, 125]);
+    assert_eq!(cents.iter().sum::<i128>(), 1001);
+
+    let max = Currency::new_string("1701411834604692317316873037158841057.27", None).unwrap();
+    let parts = max.clone().allocate(&[1., 2., 3.]);
+
+    assert_eq!(
+        parts.iter().map(|x| x.minor_units()).sum::<i128>(),
+        i128::MAX
+    );
+
+    let cents = |ratios: &[f64]| {
+        c.clone()
+            .allocate(ratios)
+            .iter()
+            .map(|x| x.minor_units())
+            .collect::<Vec<i128>>()
+    };
+
+    assert_eq!(cents(&[1e-300, 1.]), vec![0, 1001]);
+    assert_eq!(cents(&[1e-300, 1e-300]), vec![501, 500]);
+    assert_eq!(cents(&[3e-300, 1e-300]), vec![751, 250]);
+    assert_eq!(cents(&[f64::MAX, f64::MAX]), vec![501, 500]);
+    assert_eq!(cents(&[f64::MAX, f64::MIN_POSITIVE, 0.]), vec![1001, 0, 0]);
+    assert_eq!(
+        max.allocate(&[1e-300, 1., 1e300])
+            .iter()
+            .map(|x| x.minor_units())
+            .sum::<i128>(),
+        i128::MAX
+    );
+}
+
+fn rate(
+    from: &str,
+    to: &str,
+    rate: &str,
+) -> ExchangeRate {
+    ExchangeRate::new(from, to, rate.parse().unwrap()).unwrap()
+}
+
+#[test]
+fn should_convert_with_exchange_rates() {
+    let usd = |v: f64| Currency::new_float(v, CurrencyOpts::for_code("USD"));
+    let table = RateTable::new()
+        .set_rate(rate("USD", "EUR", "0.9215"))
+        .set_rate(rate("usd", "jpy", "149.87"));
+
+    let eur = usd(10.).convert_to("eur", &table).unwrap();
+    let jpy = usd(10.).convert_to("JPY", &table).unwrap();
+
+    assert_eq!(eur.code(), Some("EUR"));
+    assert_eq!(eur.to_string(), "9.22");
+    assert_eq!(eur.format(), "€9.22");
+    assert_eq!(jpy.to_string(), "1499");
+    assert_eq!(usd(1.).convert_to("USD", &table).unwrap().value(), 1.);
+
+    let truncated = Currency::new_float(
+        10.,
+        CurrencyOpts::for_code("USD").map(|o| o.set_rounding_mode(RoundingMode::Floor)),
+    )
+    .convert_to("EUR", &table)
+    .unwrap();
+
+    assert_eq!(truncated.to_string(), "9.21");
+}
+
+#[test]
+fn should_invert_and_triangulate_exchange_rates() {
+    let table = RateTable::new()
+        .set_base("EUR")
+        .set_rate(rate("EUR", "USD", "1.25"))
+        .set_rate(rate("EUR", "GBP", "0.8"));
+
+    let usd_eur = table.rate("USD", "EUR").unwrap();
+    let usd_gbp = table.rate("USD", "GBP").unwrap();
+
+    assert_eq!(usd_eur.rate(), "0.8".parse().unwrap());
+    assert_eq!(usd_eur.via(), None);
+    assert_eq!(usd_gbp.rate(), "0.64".parse().unwrap());
+    assert_eq!(usd_gbp.via(), Some("EUR".to_string()));
+    assert_eq!(table.rate("USD", "JPY"), None);
+
+    let usd = Currency::new_float(100., CurrencyOpts::for_code("USD"));
+    let gbp = usd.convert_with(&usd_gbp, None).unwrap();
+
+    assert_eq!(gbp.value(), 64.);
+    assert_eq!(gbp.code(), Some("GBP"));
+}
+
+#[test]
+fn should_report_exchange_errors() {
+    let table = RateTable::new().set_rate(rate("USD", "EUR", "0.92"));
+    let usd = Currency::new_float(10., CurrencyOpts::for_code("USD"));
+
+    assert_eq!(
+        Currency::new_float(10., None)
+            .convert_to("EUR", &table)
+            .unwrap_err(),
+        CurrencyErr::MissingCode
+    );
+    assert_eq!(
+        usd.convert_to("JPY", &table).unwrap_err(),
+        CurrencyErr::RateNotFound {
+            from: "USD".to_string(),
+            to: "JPY".to_string()
+        }
+    );
+    assert_eq!(
+        usd.convert_with(&rate("GBP", "EUR", "1.17"), None)
+            .unwrap_err(),
+        CurrencyErr::CurrencyMismatch {
+            expected: "GBP".to_string(),
+            found: "USD".to_string()
+        }
+    );
+    assert_eq!(
+        ExchangeRate::new("USD", "EUR", "0".parse().unwrap()).unwrap_err(),
+        CurrencyErr::InvalidRate
+    );
+}
+
+const ECB_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<gesmes:Envelope xmlns:gesmes="http://www.gesmes.org/xml/2002-08-01" xmlns="http://www.ecb.int/vocabulary/2002-08-01/eurofxref">
+    <gesmes:subject>Reference rates</gesmes:subject>
+    <Cube>
+        <Cube time="2024-01-03">
+            <Cube currency="USD" rate="1.0919"/>
+            <Cube currency="JPY" rate="155.17"/>
+        </Cube>
+        <Cube time='2024-01-02'>
+            <Cube currency='USD' rate='1.0956'/>
+            <Cube currency='JPY' rate='155.52'/>
+        </Cube>
+    </Cube>
+</gesmes:Envelope>"#;
+
+#[test]
+fn should_parse_rate_dates() {
+    let date = "2024-02-29".parse::<RateDate>().unwrap();
+
+    assert_eq!((date.year(), date.month(), date.day()), (2024, 2, 29));
+    assert_eq!(date.to_string(), "2024-02-29");
+    assert_eq!(RateDate::new(2023, 2, 29), None);
+    assert_eq!(RateDate::new(1900, 2, 29), None);
+    assert!(RateDate::new(2000, 2, 29).is_some());
+    assert!("2024-1-02".parse::<RateDate>().is_err());
+    assert!("2024-13-01".parse::<RateDate>().is_err());
+    assert!("yesterday".parse::<RateDate>().is_err());
+}
+
+#[test]
+fn should_load_ecb_xml_rates() {
+    let table = DatedRateTable::from_ecb_xml(ECB_XML.as_bytes()).unwrap();
+    let date = |d: &str| d.parse::<RateDate>().unwrap();
+
+    assert_eq!(table.dates(), vec![date("2024-01-02"), date("2024-01-03")]);
+    assert_eq!(table.base(), Some("EUR".to_string()));
+    assert_eq!(
+        table
+            .rate_on(date("2024-01-02"), "EUR", "USD")
+            .unwrap()
+            .rate(),
+        "1.0956".parse().unwrap()
+    );
+    assert_eq!(
+        table
+            .rate_on(date("2024-01-06"), "EUR", "USD")
+            .unwrap()
+            .rate(),
+        "1.0919".parse().unwrap()
+    );
+    assert_eq!(table.rate_on(date("2024-01-01"), "EUR", "USD"), None);
+
+    let usd = Currency::new_float(100., CurrencyOpts::for_code("USD"));
+    let jpy = usd
+        .convert_to("JPY", table.on(date("2024-01-02")).unwrap())
+        .unwrap();
+
+    assert_eq!(jpy.to_string(), "14195");
+}
+
+#[test]
+fn should_load_csv_rates_from_files() {
+    let path = std::env::temp_dir().join(format!("currency_rs_rates_{}.csv", std::process::id()));
+
+    std::fs::write(
+        &path,
+        "date,base,quote,rate\n2024-01-02,USD,EUR,0.9127\n\n2024-01-03, usd, gbp, 0.7890\n",
+    )
+    .unwrap();
+
+    let table = DatedRateTable::from_csv_file(&path).unwrap();
+
+    std::fs::remove_file(&path).unwrap();
+
+    let date = "2024-01-03".parse::<RateDate>().unwrap();
+
+    assert_eq!(table.base(), Some("USD".to_string()));
+    assert_eq!(
+        table.rate_on(date, "USD", "GBP").unwrap().rate(),
+        "0.789".parse().unwrap()
+    );
+    assert_eq!(table.rate_on(date, "USD", "EUR"), None);
+
+    assert_eq!(
+        DatedRateTable::from_csv("2024-01-02,USD,EUR,abc".as_bytes()).unwrap_err(),
+        CurrencyErr::invalid("line 1: invalid decimal value: \"abc\"")
+    );
+    assert_eq!(
+        DatedRateTable::from_csv("date,base,quote,rate\n2024-01-02,USD,EUR".as_bytes())
+            .unwrap_err(),
+        CurrencyErr::invalid("line 2: expected date,base,quote,rate")
+    );
+    assert!(matches!(
+        DatedRateTable::from_csv_file(&path),
+        Err(CurrencyErr::Io { .. })
+    ));
+}
+
+#[test]
+fn should_add_mixed_currencies_to_a_money_bag() {
+    let cur = |v: f64, code: &str| Currency::new_float(v, CurrencyOpts::for_code(code));
+
+    let mut bag = MoneyBag::new();
+
+    bag += cur(10.5, "USD");
+    bag += cur(3.25, "EUR");
+    bag += cur(0.75, "usd");
+    bag -= cur(1.25, "EUR");
+    bag -= cur(500., "JPY");
+
+    assert_eq!(bag.len(), 3);
+    assert_eq!(bag.codes(), vec!["EUR", "JPY", "USD"]);
+    assert_eq!(bag.get("usd").unwrap().value(), 11.25);
+    assert_eq!(bag.get("EUR").unwrap().value(), 2.);
+    assert_eq!(bag.get("JPY").unwrap().format(), "-¥500");
+    assert_eq!(bag.get("GBP").map(|c| c.value()), None);
+
+    let negated = -bag.clone();
+
+    assert_eq!(
+        negated.iter().map(|c| c.value()).collect::<Vec<f64>>(),
+        vec![-2., 500., -11.25]
+    );
+    assert!((bag.clone() + negated).iter().all(|c| c.minor_units() == 0));
+
+    let collected = vec![
+        cur(1., "USD"),
+        Currency::new_float(2., None),
+        cur(3., "USD"),
+    ]
+    .into_iter()
+    .collect::<MoneyBag>();
+
+    assert_eq!(
+        (&collected)
+            .into_iter()
+            .map(|c| (c.code(), c.value()))
+            .collect::<Vec<_>>(),
+        vec![(None, 2.), (Some("USD"), 4.)]
+    );
+}
+
+#[test]
+fn should_collapse_a_money_bag() {
+    let cur = |v: f64, code: &str| Currency::new_float(v, CurrencyOpts::for_code(code));
+
+    let table = RateTable::new()
+        .set_base("EUR")
+        .set_rate(rate("EUR", "USD", "1.25"))
+        .set_rate(rate("EUR", "JPY", "160"));
+
+    let bag = MoneyBag::new() + cur(12.5, "USD") + cur(1., "EUR") + cur(1600., "JPY");
+
+    let total = bag.collapse("EUR", &table).unwrap();
+
+    assert_eq!(total.code(), Some("EUR"));
+    assert_eq!(total.value(), 21.);
+    assert_eq!(bag.collapse("USD", &table).unwrap().value(), 26.25);
+    assert_eq!(
+        MoneyBag::<i128>::new()
+            .collapse("GBP", &table)
+            .unwrap()
+            .value(),
+        0.
+    );
+
+    assert_eq!(
+        (bag.clone() + cur(1., "CHF"))
+            .collapse("EUR", &table)
+            .unwrap_err(),
+        CurrencyErr::RateNotFound {
+            from: "CHF".to_string(),
+            to: "EUR".to_string()
+        }
+    );
+    assert_eq!(
+        (bag + Currency::new_float(1., None))
+            .collapse("EUR", &table)
+            .unwrap()
+            .value(),
+        22.
+    );
+}
+
+#[test]
+fn should_add_exact_amounts_to_a_money_bag_with_from_cents() {
+    let cents = Some(CurrencyOpts::new().set_code("USD").set_from_cents(true));
+    let c = Currency::new_float(150., cents);
+
+    let bag = vec![c.clone(), c.clone()].into_iter().collect::<MoneyBag>();
+    assert_eq!(bag.get("USD").unwrap().value(), 3.);
+
+    let mut bag = MoneyBag::new() + c.clone();
+    bag += c.clone();
+    bag -= Currency::new_float(0.5, CurrencyOpts::for_code("USD"));
+    bag -= c;
+    assert_eq!(bag.get("USD").unwrap().value(), 1.);
+}
+
+#[test]
+fn should_format_with_locales() {
+    let cur = |v: f64, code: &str| Currency::new_float(v, CurrencyOpts::for_code(code));
+
+    let chf = cur(1234567.891, "CHF");
+
+    assert_eq!(
+        chf.format_locale("de-CH").unwrap(),
+        "CHF\u{a0}1\u{2019}234\u{2019}567.89"
+    );
+    assert_eq!(cur(-5., "CHF").format_locale("de_ch").unwrap(), "CHF-5.00");
+    assert_eq!(
+        chf.format_locale("fr-CH").unwrap(),
+        "1\u{202f}234\u{202f}567,89\u{a0}CHF"
+    );
+    assert_eq!(
+        cur(1234.5, "EUR").format_locale("de-DE").unwrap(),
+        "1.234,50\u{a0}€"
+    );
+    assert_eq!(
+        cur(-1234.5, "EUR").format_locale("nl-NL").unwrap(),
+        "€\u{a0}-1.234,50"
+    );
+    assert_eq!(
+        cur(1234.5, "EUR").format_locale("es-ES").unwrap(),
+        "1234,50\u{a0}€"
+    );
+    assert_eq!(
+        cur(12345.5, "EUR").format_locale("es").unwrap(),
+        "12.345,50\u{a0}€"
+    );
+    assert_eq!(
+        cur(-1234.5, "SEK").format_locale("sv-SE").unwrap(),
+        "\u{2212}1\u{a0}234,50\u{a0}kr"
+    );
+    assert_eq!(
+        cur(1234567.5, "INR").format_locale("en-IN").unwrap(),
+        "₹12,34,567.50"
+    );
+    assert_eq!(
+        cur(1234567., "JPY").format_locale("ja-JP").unwrap(),
+        "¥1,234,567"
+    );
+    assert_eq!(
+        cur(-1234.5, "USD").format_locale("en-US").unwrap(),
+        "-$1,234.50"
+    );
+    assert_eq!(
+        cur(1234.5, "CAD").format_locale("en-US").unwrap(),
+        "CA$1,234.50"
+    );
+    assert_eq!(
+        cur(1234.5, "CHF").format_locale("en-US").unwrap(),
+        "CHF\u{a0}1,234.50"
+    );
+    assert_eq!(
+        cur(1234.5, "CHF").format_locale("de-LI").unwrap(),
+        "1.234,50\u{a0}CHF"
+    );
+    assert_eq!(
+        cur(1., "USD").format_locale("xx-YY").unwrap_err(),
+        CurrencyErr::UnknownLocale("xx-YY".to_string())
+    );
+}
+
+#[test]
+fn should_look_up_locales() {
+    let locale = Locale::from_tag("pt_br").unwrap();
+
+    assert_eq!(locale.tag(), "pt-BR");
+    assert_eq!(locale.language(), "pt");
+    assert_eq!(locale.currency(), "BRL");
+    assert_eq!((locale.decimal(), locale.group()), (",", "."));
+    assert_eq!(Locale::from_tag("de").unwrap().tag(), "de-DE");
+    assert_eq!(Locale::from_tag("tlh"), None);
+    assert!(Locale::all()
+        .iter()
+        .all(|l| IsoCurrency::from_code(l.currency()).is_some()));
+
+    let brl = Currency::new_float(-0.004, CurrencyOpts::for_code("BRL"));
+
+    assert_eq!(brl.format_with_locale(locale), "R$\u{a0}0,00");
+}
+
+#[test]
+fn should_format_grouping_patterns() {
+    let c = |v: f64, grouping: Grouping| {
+        Currency::new_float(v, Some(CurrencyOpts::new().set_grouping(grouping))).format()
+    };
+
+    assert_eq!(c(1234567.89, Grouping::THOUSANDS), "$1,234,567.89");
+    assert_eq!(c(1234567.89, Grouping::VEDIC), "$12,34,567.89");
+    assert_eq!(c(123456789., Grouping::MYRIAD), "$1,2345,6789.00");
+    assert_eq!(c(1234., Grouping::MYRIAD), "$1234.00");
+    assert_eq!(c(1234., Grouping::THOUSANDS_MIN_2), "$1234.00");
+    assert_eq!(c(12345., Grouping::THOUSANDS_MIN_2), "$12,345.00");
+    assert_eq!(c(-1234567., Grouping::NONE), "-$1234567.00");
+    assert_eq!(c(1234567., Grouping::new(2, 0, 0)), "$1,23,45,67.00");
+    assert_eq!(Grouping::new(2, 0, 0), Grouping::new(2, 2, 1));
+}
+
+#[test]
+fn should_keep_use_vedic_as_a_grouping_preset() {
+    let vedic = CurrencyOpts::new().set_use_vedic(true);
+
+    assert_eq!(vedic.grouping(), Grouping::VEDIC);
+    assert!(vedic.use_vedic());
+    assert_eq!(vedic.set_use_vedic(false).grouping(), Grouping::THOUSANDS);
+    assert_eq!(
+        CurrencyOpts::new()
+            .set_grouping(Grouping::MYRIAD)
+            .set_use_vedic(false)
+            .grouping(),
+        Grouping::MYRIAD
+    );
+    assert!(!CurrencyOpts::new().use_vedic());
+    assert_eq!(
+        Locale::from_tag("hi-IN").unwrap().grouping(),
+        Grouping::VEDIC
+    );
+}
+
+fn strict_err(
+    value: &str,
+    opts: CurrencyOpts,
+) -> (ParseErrKind, Option<usize>) {
+    match Currency::new_string(value, Some(opts.set_strict(true))).unwrap_err() {
+        CurrencyErr::ParseErr(e) => (e.kind(), e.offset()),
+        e => panic!("unexpected error {e:?}"),
+    }
+}
+
+#[test]
+fn should_parse_well_formed_amounts_strictly() {
+    let c = |v: &str, opts: CurrencyOpts| {
+        Currency::new_string(v, Some(opts.set_strict(true)))
+            .unwrap()
+            .value()
+    };
+    let usd = || CurrencyOpts::for_code("USD").unwrap();
+    let euro = || {
+        CurrencyOpts::for_code("EUR")
+            .unwrap()
+            .set_separator(".")
+            .set_decimal(",")
+    };
+
+    assert_eq!(c("1234.56", usd()), 1234.56);
+    assert_eq!(c("  $1,234,567.89 ", usd()), 1234567.89);
+    assert_eq!(c("-$1,234.5", usd()), -1234.5);
+    assert_eq!(c("$ -12", usd()), -12.);
+    assert_eq!(c("($12.34)", usd()), -12.34);
+    assert_eq!(c("USD 12.34", usd()), 12.34);
+    assert_eq!(c("12.345 usd", usd()), 12.35);
+    assert_eq!(c("1.234,56 €", euro()), 1234.56);
+    assert_eq!(c("€1.234.567", euro()), 1234567.);
+    assert_eq!(
+        c("12,34,567.89", CurrencyOpts::new().set_use_vedic(true)),
+        1234567.89
+    );
+    assert_eq!(c("1234 GBP", CurrencyOpts::new()), 1234.);
+    assert_eq!(c("123", CurrencyOpts::new().set_from_cents(true)), 1.23);
+}
+
+#[test]
+fn should_report_strict_parse_violations() {
+    let usd = || CurrencyOpts::for_code("USD").unwrap();
+
+    assert_eq!(strict_err("", usd()), (ParseErrKind::Empty, Some(0)));
+    assert_eq!(strict_err(" $ ", usd()), (ParseErrKind::Empty, Some(3)));
+    assert_eq!(
+        strict_err("12abc34", usd()),
+        (ParseErrKind::CodeMismatch, Some(2))
+    );
+    assert_eq!(
+        strict_err("12abc34", CurrencyOpts::new()),
+        (ParseErrKind::UnknownCode, Some(2))
+    );
+    assert_eq!(
+        strict_err("12 34", usd()),
+        (ParseErrKind::UnexpectedChar, Some(3))
+    );
+    assert_eq!(
+        strict_err("1.2.3", usd()),
+        (ParseErrKind::MultipleDecimals, Some(3))
+    );
+    assert_eq!(
+        strict_err(".5", usd()),
+        (ParseErrKind::MissingDigits, Some(0))
+    );
+    assert_eq!(
+        strict_err("5.", usd()),
+        (ParseErrKind::MissingDigits, Some(2))
+    );
+    assert_eq!(
+        strict_err("1,23,456", usd()),
+        (ParseErrKind::InvalidGroup, Some(1))
+    );
+    assert_eq!(
+        strict_err("1234,567", usd()),
+        (ParseErrKind::InvalidGroup, Some(0))
+    );
+    assert_eq!(
+        strict_err("1,234.5,6", usd()),
+        (ParseErrKind::InvalidGroup, Some(7))
+    );
+    assert_eq!(
+        strict_err("--5", usd()),
+        (ParseErrKind::MisplacedSign, Some(1))
+    );
+    assert_eq!(
+        strict_err("5-", usd()),
+        (ParseErrKind::MisplacedSign, Some(1))
+    );
+    assert_eq!(
+        strict_err("(-5)", usd()),
+        (ParseErrKind::MisplacedSign, Some(1))
+    );
+    assert_eq!(
+        strict_err("(5", usd()),
+        (ParseErrKind::UnbalancedParenthesis, Some(2))
+    );
+    assert_eq!(
+        strict_err("$5 EUR", usd()),
+        (ParseErrKind::CodeMismatch, Some(3))
+    );
+    assert_eq!(
+        strict_err("$$5", usd()),
+        (ParseErrKind::UnexpectedChar, Some(1))
+    );
+    assert_eq!(
+        strict_err("1,234", CurrencyOpts::new().set_grouping(Grouping::NONE)),
+        (ParseErrKind::InvalidGroup, Some(1))
+    );
+    assert_eq!(
+        strict_err("1701411834604692317316873037158841058", usd()),
+        (ParseErrKind::Overflow, Some(0))
+    );
+    assert!(matches!(
+        Currency::<i64>::from_string("$ 99999999999999999999", Some(usd().set_strict(true))),
+        Err(CurrencyErr::ParseErr(e)) if e.kind() == ParseErrKind::Overflow && e.offset() == Some(2)
+    ));
+
+    let err = Currency::new_string("1.2.3", Some(usd().set_strict(true))).unwrap_err();
+
+    assert!(
+        matches!(err, CurrencyErr::ParseErr(e) if e.message() == "second decimal separator at byte 3")
+    );
+    assert_eq!(
+        Currency::new_string("12abc34", None).unwrap().value(),
+        1234.
+    );
+}
+
+#[test]
+fn should_describe_errors() {
+    use std::error::Error;
+
+    let mismatch = CurrencyErr::CurrencyMismatch {
+        expected: "USD".to_string(),
+        found: "EUR".to_string(),
+    };
+
+    assert_eq!(
+        mismatch.to_string(),
+        "currency mismatch: expected USD, found EUR"
+    );
+    assert_eq!(CurrencyErr::DivisionByZero.to_string(), "division by zero");
+    assert_eq!(
+        CurrencyErr::invalid("invalid date: \"x\"")
+            .source()
+            .map(|e| e.to_string()),
+        Some("invalid date: \"x\"".to_string())
+    );
+
+    let missing = std::env::temp_dir().join("currency_rs_missing_rates.csv");
+    let err = DatedRateTable::from_csv_file(&missing).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        format!("could not read {}", missing.display())
+    );
+    assert_eq!(
+        err.source()
+            .and_then(|e| e.downcast_ref::<std::io::Error>())
+            .map(|e| e.kind()),
+        Some(std::io::ErrorKind::NotFound)
+    );
+
+    let boxed = || -> Result<f64, Box<dyn Error + Send + Sync>> {
+        Ok(Currency::new_string("1.2.3", Some(CurrencyOpts::new().set_strict(true)))?.value())
+    };
+
+    let chain = |err: &dyn Error| {
+        let mut messages = vec![err.to_string()];
+        let mut source = err.source();
+
+        while let Some(e) = source {
+            messages.push(e.to_string());
+            source = e.source();
+        }
+
+        messages.join(": ")
+    };
+
+    assert_eq!(
+        chain(boxed().unwrap_err().as_ref()),
+        "could not parse the value: second decimal separator at byte 3"
+    );
+
+    let parse =
+        Currency::new_string("1.2.3", Some(CurrencyOpts::new().set_strict(true))).unwrap_err();
+
+    assert_eq!(
+        parse
+            .source()
+            .and_then(|e| e.downcast_ref::<ParseError>())
+            .map(|e| (e.kind(), e.offset())),
+        Some((ParseErrKind::MultipleDecimals, Some(3)))
+    );
+    assert!(CurrencyErr::DivisionByZero.source().is_none());
+}
+
+#[test]
+fn should_report_error_kinds_from_constructors() {
+    let kind = |v: &str| match Currency::new_string(
+        v,
+        Some(CurrencyOpts::new().set_error_on_invalid(true)),
+    ) {
+        Err(CurrencyErr::ParseErr(e)) => e.kind(),
+        r => panic!("unexpected result {r:?}"),
+    };
+
+    assert_eq!(kind(""), ParseErrKind::Empty);
+    assert_eq!(kind("abc"), ParseErrKind::Empty);
+    assert_eq!(kind("1.2.3"), ParseErrKind::MultipleDecimals);
+    assert_eq!(kind("1-2"), ParseErrKind::UnexpectedChar);
+
+    assert_eq!(Currency::try_new_float(1.005, None).unwrap().value(), 1.01);
+    assert_eq!(
+        Currency::try_new_float(f64::NAN, None).unwrap_err(),
+        CurrencyErr::NonFiniteNumber
+    );
+    assert_eq!(
+        Currency::<i64>::try_from_float(1e18, None).unwrap_err(),
+        CurrencyErr::Overflow
+    );
+    assert_eq!(
+        Currency::<f64>::try_from_float(1e15, None).unwrap_err(),
+        CurrencyErr::PrecisionLoss
+    );
+
+    let invalid = |opts: CurrencyOpts| {
+        matches!(
+            Currency::new_string("1", Some(opts)),
+            Err(CurrencyErr::InvalidOptions(_))
+        )
+    };
+
+    assert!(invalid(CurrencyOpts::new().set_decimal(",")));
+    assert!(invalid(CurrencyOpts::new().set_decimal("")));
+    assert!(invalid(CurrencyOpts::new().set_separator("0")));
+    assert!(invalid(CurrencyOpts::new().set_precision(39)));
+    assert!(invalid(CurrencyOpts::new().set_increment(-0.05)));
+    assert!(invalid(CurrencyOpts::new().set_pattern("!")));
+    assert!(!invalid(CurrencyOpts::new().set_separator("")));
+    assert_eq!(
+        Currency::try_new_float(1., Some(CurrencyOpts::new().set_decimal(","))).unwrap_err(),
+        CurrencyErr::InvalidOptions("decimal and group separators are the same".to_string())
+    );
+}
+
+#[test]
+fn should_compare_exact_values() {
+    let usd = |value: f64| Currency::new_float(value, Some(CurrencyOpts::new().set_code("USD")));
+
+    assert_eq!(
+        Currency::new_float(1.5, None),
+        Currency::new_float(1.5, Some(CurrencyOpts::new().set_precision(3)))
+    );
+    assert_ne!(Currency::new_float(1.5, None), usd(1.5));
+    assert!(usd(0.1) < usd(0.2));
+    assert!(Currency::new_float(0.1, None) + 0.2 == Currency::new_float(0.3, None));
+
+    let mut values = vec![usd(3.), usd(-1.), usd(2.)];
+    values.sort();
+
+    assert_eq!(values, vec![usd(-1.), usd(2.), usd(3.)]);
+    assert_eq!(values.iter().max(), Some(&usd(3.)));
+    assert_eq!(usd(5.).clamp(usd(0.), usd(4.)), usd(4.));
+    assert_eq!(usd(1.).min(usd(-1.)), usd(-1.));
+
+    let set: std::collections::HashSet<_> = [
+        Currency::new_float(1.5, None),
+        Currency::new_float(1.5, Some(CurrencyOpts::new().set_precision(4))),
+        Currency::new_float(1.25, None),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(set.len(), 2);
+
+    let map: std::collections::BTreeMap<_, _> = [(usd(2.), "b"), (usd(1.), "a")].into();
+
+    assert_eq!(map.values().copied().collect::<Vec<_>>(), vec!["a", "b"]);
+    assert_eq!(
+        Currency::<i64>::from_float(1., None),
+        Currency::<i64>::from_float(1., None)
+    );
+}
+
+#[test]
+fn should_check_sign() {
+    let c = Currency::new_float(-2.51, None);
+
+    assert!(c.is_negative());
+    assert!(!c.is_positive());
+    assert!(!c.is_zero());
+    assert_eq!(c.signum(), -1);
+    assert_eq!(c.abs().value(), 2.51);
+    assert!(Currency::new_float(0.001, None).is_zero());
+    assert_eq!(Currency::new_float(0., None).signum(), 0);
+    assert_eq!(Currency::new_float(3., None).signum(), 1);
+    assert_eq!(
+        Currency::<i64>::from_string(
+            &i64::MIN.to_string(),
+            Some(CurrencyOpts::new().set_from_cents(true))
+        )
+        .unwrap()
+        .abs()
+        .minor_units(),
+        i64::MAX as i128
+    );
+}
+
+#[test]
+fn should_sum_currencies() {
+    let jpy = |value: f64| Currency::new_float(value, CurrencyOpts::for_code("JPY"));
+
+    let items = vec![jpy(1200.), jpy(350.), jpy(15000.)];
+
+    let total: Currency = items.iter().sum();
+
+    assert_eq!(total.format(), "¥16,550");
+    assert_eq!(items.into_iter().sum::<Currency>(), jpy(16550.));
+    assert_eq!(
+        [1.23, 4.56]
+            .iter()
+            .map(|v| Currency::new_float(*v, None))
+            .sum::<Currency>()
+            .value(),
+        5.79
+    );
+
+    let empty: Currency = Vec::<Currency>::new().into_iter().sum();
+
+    assert!(empty.is_zero());
+    assert_eq!(empty.code(), None);
+}
+
+#[test]
+fn should_try_sum_currencies() {
+    let usd = Currency::new_float(1., CurrencyOpts::for_code("USD"));
+    let eur = Currency::new_float(2., CurrencyOpts::for_code("EUR"));
+
+    assert_eq!(
+        Currency::try_sum([&usd, &usd]).unwrap(),
+        Currency::new_float(2., CurrencyOpts::for_code("USD"))
+    );
+    assert_eq!(
+        Currency::try_sum(vec![usd.clone(), eur]).unwrap_err(),
+        CurrencyErr::CurrencyMismatch {
+            expected: "USD".to_string(),
+            found: "EUR".to_string()
+        }
+    );
+    assert!(Currency::try_sum(Vec::<Currency>::new()).unwrap().is_zero());
+    assert_eq!(
+        Currency::<i64>::try_sum([
+            Currency::<i64>::from_float(9e16, None),
+            Currency::<i64>::from_float(9e16, None)
+        ])
+        .unwrap_err(),
+        CurrencyErr::Overflow
+    );
+}
+
+#[test]
+fn should_sum_exact_amounts_with_from_cents() {
+    let cents = Some(CurrencyOpts::new().set_from_cents(true));
+    let items = vec![
+        Currency::new_float(150., cents.clone()),
+        Currency::new_float(150., cents),
+    ];
+
+    assert_eq!(Currency::try_sum(&items).unwrap().value(), 3.);
+    assert_eq!(items.iter().sum::<Currency>().value(), 3.);
+    assert_eq!(items.into_iter().sum::<Currency>().value(), 3.);
+
+    let mixed = [
+        Currency::new_float(1.005, Some(CurrencyOpts::new().set_precision(3))),
+        Currency::new_float(1.5, None),
+    ];
+
+    assert_eq!(mixed.iter().sum::<Currency>().value(), 2.505);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn should_serialize_currencies() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Invoice {
+        total: Currency,
+        #[serde(with = "crate::currency_ser::decimal")]
+        net: Currency,
+        #[serde(with = "crate::currency_ser::minor_units")]
+        tax: Currency<i64>,
+    }
+
+    let invoice = Invoice {
+        total: Currency::new_float(1234.5, CurrencyOpts::for_code("JPY")),
+        net: Currency::new_float(0.1, None) + 0.2,
+        tax: Currency::<i64>::from_float(-12.34, None),
+    };
+
+    let json = serde_json::to_string(&invoice).unwrap();
+
+    assert_eq!(
+        json,
+        r#"{"total":{"amount":"1235","currency":"JPY"},"net":"0.30","tax":{"amount":-1234}}"#
+    );
+
+    let back: Invoice = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(back, invoice);
+    assert_eq!(back.total.format(), "¥1,235");
+    assert_eq!(
+        serde_json::from_str::<Currency>(r#"{"amount":"12.345"}"#)
+            .unwrap()
+            .value(),
+        12.345
+    );
+    assert_eq!(
+        serde_json::from_str::<Currency>(r#"{"amount":"12.5","currency":"USD"}"#)
+            .unwrap()
+            .format(),
+        "$12.50"
+    );
+    assert!(serde_json::from_str::<Currency>(r#"{"amount":"1.2.3"}"#)
+        .unwrap_err()
+        .to_string()
+        .starts_with(r#"invalid amount "1.2.3""#));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn should_round_trip_serialized_precision_and_codes() {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Amounts {
+        total: Currency,
+        #[serde(with = "crate::currency_ser::decimal")]
+        net: Currency,
+        #[serde(with = "crate::currency_ser::minor_units")]
+        tax: Currency,
+    }
+
+    let exact = |cur: &Currency| Decimal::new(cur.minor_units(), cur.opts.precision());
+    let round_trip = |cur: Currency| {
+        let amounts = Amounts {
+            total: cur.clone(),
+            net: cur.clone(),
+            tax: cur,
+        };
+        let json = serde_json::to_string(&amounts).unwrap();
+        let back: Amounts = serde_json::from_str(&json).unwrap();
+
+        // the decimal string has no code, its value is kept but not its options
+        assert_eq!(back.total, amounts.total);
+        assert_eq!(back.total.minor_units(), amounts.total.minor_units());
+        assert_eq!(back.net.code(), None);
+        assert_eq!(exact(&back.net), exact(&amounts.net));
+        assert_eq!(back.tax, amounts.tax);
+        assert_eq!(back.tax.minor_units(), amounts.tax.minor_units());
+        json
+    };
+
+    let jpy = Currency::new_float(1234., CurrencyOpts::for_code("JPY"));
+    let kwd = Currency::new_float(1.234, CurrencyOpts::for_code("KWD"));
+    let fine = Currency::new_float(1.2345, Some(CurrencyOpts::new().set_precision(4)));
+    let btc = Currency::new_float(
+        0.12345678,
+        Some(CurrencyOpts::new().set_code("BTC").set_precision(8)),
+    );
+
+    assert_eq!(
+        round_trip(jpy),
+        r#"{"total":{"amount":"1234","currency":"JPY"},"net":"1234","tax":{"amount":1234,"currency":"JPY"}}"#
+    );
+    assert_eq!(
+        round_trip(kwd),
+        r#"{"total":{"amount":"1.234","currency":"KWD"},"net":"1.234","tax":{"amount":1234,"currency":"KWD"}}"#
+    );
+    assert_eq!(
+        round_trip(fine),
+        r#"{"total":{"amount":"1.2345"},"net":"1.2345","tax":{"amount":12345,"scale":4}}"#
+    );
+    round_trip(btc);
+
+    let btc: Currency = serde_json::from_str(r#"{"amount":"0.5","currency":"BTC"}"#).unwrap();
+    assert_eq!(btc.code(), Some("BTC"));
+    assert_eq!(btc.value(), 0.5);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn should_serialize_opts_and_errors() {
+    let opts = CurrencyOpts::new()
+        .set_symbol("€")
+        .set_decimal(",")
+        .set_separator(".")
+        .set_grouping(Grouping::VEDIC)
+        .set_rounding_mode(RoundingMode::HalfEven);
+
+    let back: CurrencyOpts = serde_json::from_str(&serde_json::to_string(&opts).unwrap()).unwrap();
+
+    assert_eq!(
+        Currency::new_float(1234567.125, Some(back)).format(),
+        Currency::new_float(1234567.125, Some(opts)).format()
+    );
+
+    let partial: CurrencyOpts =
+        serde_json::from_str(r#"{"precision":0,"grouping":{"primary":4}}"#).unwrap();
+
+    assert_eq!(partial.precision(), 0);
+    assert_eq!(partial.grouping(), Grouping::MYRIAD);
+    assert_eq!(partial.symbol(), "$");
+
+    let nickel: CurrencyOpts = serde_json::from_str(r#"{"increment":0.05}"#).unwrap();
+
+    assert_eq!(Currency::new_float(1.23, Some(nickel)).format(), "$1.25");
+
+    for invalid in [
+        r#"{"precision":200}"#,
+        r#"{"decimal":""}"#,
+        r#"{"separator":".","decimal":"."}"#,
+        r#"{"increment":-0.05}"#,
+        r#"{"pattern":"!"}"#,
+    ] {
+        let err = serde_json::from_str::<CurrencyOpts>(invalid).unwrap_err();
+
+        assert!(
+            err.to_string().starts_with("invalid currency options"),
+            "{invalid}: {err}"
+        );
+    }
+
+    let err =
+        Currency::new_string("1.2.3", Some(CurrencyOpts::new().set_strict(true))).unwrap_err();
+
+    let back: CurrencyErr = serde_json::from_str(&serde_json::to_string(&err).unwrap()).unwrap();
+
+    assert_eq!(back, err);
+    assert_eq!(
+        serde_json::to_string(&CurrencyErr::Overflow).unwrap(),
+        r#""Overflow""#
+    );
+}
+
+#[test]
+fn should_operate_on_references() {
+    let a = Currency::new_float(1.23, CurrencyOpts::for_code("USD"));
+    let b = Currency::new_float(4.56, CurrencyOpts::for_code("USD"));
+
+    assert_eq!((&a + &b).value(), 5.79);
+    assert_eq!((&b - &a).value(), 3.33);
+    assert_eq!((a.clone() + &b).value(), 5.79);
+    assert_eq!((&b - a.clone()).value(), 3.33);
+    assert_eq!((&a * 2.).value(), 2.46);
+    assert_eq!((&b / 2.).value(), 2.28);
+    assert_eq!((&a + 1.).value(), 2.23);
+    assert_eq!((&a - 1.).value(), 0.23);
+    assert_eq!((&a + &b).code(), Some("USD"));
+
+    let mut total = Currency::new_float(0., CurrencyOpts::for_code("USD"));
+
+    for item in [&a, &b] {
+        total += item;
+    }
+
+    total -= &a;
+
+    assert_eq!(total, b);
+}
+
+#[test]
+fn should_assign_in_place() {
+    let mut c = Currency::new_float(10., None);
+
+    c += 0.1;
+    c -= 0.05;
+    c *= 3.;
+    c /= 2.;
+
+    assert_eq!(c.value(), 15.08);
+
+    c /= 0.;
+
+    assert_eq!(c.value(), 15.08);
+
+    c += Currency::new_float(0.02, Some(CurrencyOpts::new().set_precision(3)));
+
+    assert_eq!(c.value(), 15.1);
+}
+
+#[test]
+#[should_panic(expected = "cannot add currencies with different codes")]
+fn should_panic_on_reference_code_mismatch() {
+    let _ = &Currency::new_float(1., CurrencyOpts::for_code("USD"))
+        + &Currency::new_float(1., CurrencyOpts::for_code("EUR"));
+}
+
+#[test]
+fn should_share_opts() {
+    let opts = CurrencyOpts::new().set_symbol("€").set_code("EUR");
+    let shared = opts.clone();
+    let changed = opts.clone().set_symbol("EUR ");
+
+    assert_eq!(opts.symbol(), "€");
+    assert_eq!(shared.symbol(), "€");
+    assert_eq!(changed.symbol(), "EUR ");
+    assert_eq!(changed.code(), Some("EUR"));
+    assert_eq!(CurrencyOpts::new().set_precision(3).precision(), 3);
+    assert_eq!(CurrencyOpts::new().precision(), 2);
+
+    let values: Vec<Currency> = (1..=3)
+        .map(|i| Currency::new_float(i as f64, Some(opts.clone())))
+        .collect();
+
+    assert_eq!(values[2].format(), "€3.00");
+    assert_eq!(values[0].code(), Some("EUR"));
+}
+
+#[test]
+fn should_read_lenient_numbers() {
+    use crate::currency_parser::lenient_number;
+
+    let cases = [
+        ("$1,234.50", ".", "1234.50"),
+        ("(€1.234,50)", ",", "-1234.50"),
+        ("(1) and (2)", ".", "-12"),
+        ("(1\n2)", ".", "12"),
+        ("abc", ".", ""),
+        ("1 234,5 kr", ",", "1234.5"),
+        ("12Kr50", "Kr", "12.50"),
+        ("--1.2", ".", "--1.2"),
+    ];
+
+    for (value, decimal, expected) in cases {
+        assert_eq!(lenient_number(value, decimal), expected, "{value}");
+    }
+
+    assert_eq!(
+        Currency::new_string("(1,234.56)", None).unwrap().value(),
+        -1234.56
+    );
+}
+
+#[cfg(feature = "fancy-regex")]
+#[test]
+fn should_match_the_regex_parser() {
+    for (value, decimal) in [
+        ("$1,234.50", "."),
+        ("(€1.234,50)", ","),
+        ("(1) and (2)", "."),
+        ("abc", "."),
+        ("1 234,5 kr", ","),
+        ("-12.3.4", "."),
+    ] {
+        assert_eq!(
+            crate::currency_parser::lenient_number(value, decimal),
+            crate::currency_regex::lenient_number(value, decimal),
+            "{value}"
+        );
+    }
+}
+
+#[test]
+fn should_write_formatted() {
+    let values = [
+        Currency::new_float(1234567.891, None),
+        Currency::new_float(-0.5, None),
+        Currency::new_float(1234567.5, Some(CurrencyOpts::new().set_use_vedic(true))),
+        Currency::new_float(
+            -98765.4321,
+            Some(
+                CurrencyOpts::new()
+                    .set_precision(3)
+                    .set_symbol("€")
+                    .set_separator(".")
+                    .set_decimal(",")
+                    .set_pattern("# !")
+                    .set_negative_pattern("(# !)"),
+            ),
+        ),
+        Currency::new_float(1234.5, Some(CurrencyOpts::new().set_precision(0))),
+        Currency::new_float(1.23, Some(CurrencyOpts::new().set_increment(0.05))),
+        Currency::new_float(
+            12345678.,
+            Some(CurrencyOpts::new().set_grouping(Grouping::MYRIAD)),
+        ),
+    ];
+
+    for value in &values {
+        let mut formatted = String::new();
+
+        value.write_formatted(&mut formatted).unwrap();
+
+        assert_eq!(formatted, value.format());
+    }
+
+    assert_eq!(values[0].format(), "$1,234,567.89");
+    assert_eq!(values[2].format(), "$12,34,567.50");
+    assert_eq!(values[3].format(), "(98.765,432 €)");
+    assert_eq!(values[6].format(), "$1234,5678.00");
+
+    let mut csv = String::new();
+
+    for value in &values[..2] {
+        value.write_formatted(&mut csv).unwrap();
+        csv.push(';');
+    }
+
+    assert_eq!(csv, "$1,234,567.89;-$0.50;");
+}
+
+#[test]
+fn should_write_formatted_io() {
+    let mut out: Vec<u8> = Vec::new();
+
+    Currency::new_float(-1234.5, None)
+        .write_formatted_io(&mut out)
+        .unwrap();
+
+    assert_eq!(out, b"-$1,234.50");
+
+    struct Full;
+
+    impl std::io::Write for Full {
+        fn write(
+            &mut self,
+            _: &[u8],
+        ) -> std::io::Result<usize> {
+            Err(std::io::Error::new(std::io::ErrorKind::StorageFull, "full"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let err = Currency::new_float(1., None)
+        .write_formatted_io(&mut Full)
+        .unwrap_err();
+
+    assert_eq!(err.kind(), std::io::ErrorKind::StorageFull);
+    assert_eq!(Grouping::VEDIC.apply("123456789", ","), "12,34,56,789");
+}
+
+/// A global allocator that counts the allocations made on the current thread, so the tests
+/// running next to it do not add to the count.
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+unsafe impl std::alloc::GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(
+        &self,
+        layout: std::alloc::Layout,
+    ) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        std::alloc::System.alloc(layout)
+    }
+
+    unsafe fn dealloc(
+        &self,
+        ptr: *mut u8,
+        layout: std::alloc::Layout,
+    ) {
+        std::alloc::System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+#[test]
+fn should_format_without_allocating() {
+    struct Buf {
+        bytes: [u8; 64],
+        len: usize,
+    }
+
+    impl std::fmt::Write for Buf {
+        fn write_str(
+            &mut self,
+            s: &str,
+        ) -> std::fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(std::fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    let values = [
+        Currency::new_float(1234567.891, None),
+        Currency::new_float(-0.5, Some(CurrencyOpts::new().set_use_vedic(true))),
+        Currency::new_float(1.23, Some(CurrencyOpts::new().set_increment(0.05))),
+        Currency::new_float(
+            -98765.4321,
+            Some(
+                CurrencyOpts::new()
+                    .set_precision(3)
+                    .set_symbol("€")
+                    .set_separator(".")
+                    .set_decimal(",")
+                    .set_pattern("# !")
+                    .set_negative_pattern("(# !)"),
+            ),
+        ),
+    ];
+
+    let mut io_bytes = [0u8; 64];
+
+    let before = ALLOCATIONS.with(|n| n.get());
+
+    for _ in 0..100 {
+        for value in &values {
+            let mut buf = Buf {
+                bytes: [0; 64],
+                len: 0,
+            };
+
+            value.write_formatted(&mut buf).unwrap();
+            std::fmt::Write::write_fmt(&mut buf, format_args!("{value}|{value:>12.1}")).unwrap();
+            value.write_formatted_io(&mut &mut io_bytes[..]).unwrap();
+        }
+    }
+
+    assert_eq!(ALLOCATIONS.with(|n| n.get()) - before, 0);
+}
+
+#[test]
+fn should_honor_format_specifiers() {
+    let c = Currency::new_float(1234.5, None);
+    let n = Currency::new_float(-1234.5, None);
+
+    assert_eq!(format!("{c}"), "1234.50");
+    assert_eq!(format!("{c:>12}|"), "     1234.50|");
+    assert_eq!(format!("{c:12}|"), "     1234.50|");
+    assert_eq!(format!("{c:<12}|"), "1234.50     |");
+    assert_eq!(format!("{c:*^11}"), "**1234.50**");
+    assert_eq!(format!("{c:+}"), "+1234.50");
+    assert_eq!(format!("{n:+}"), "-1234.50");
+    assert_eq!(format!("{c:.4}"), "1234.5000");
+    assert_eq!(format!("{c:.0}"), "1235");
+    assert_eq!(format!("{n:.0}"), "-1235");
+    assert_eq!(format!("{n:010}"), "-001234.50");
+    assert_eq!(format!("{c:+010.1}"), "+0001234.5");
+    assert_eq!(format!("{c:2}"), "1234.50");
+    assert_eq!(format!("{:.1}", Currency::new_float(0.25, None)), "0.3");
+    assert_eq!(
+        format!(
+            "{:.1}",
+            Currency::new_float(
+                0.25,
+                Some(CurrencyOpts::new().set_rounding_mode(RoundingMode::HalfEven))
+            )
+        ),
+        "0.2"
+    );
+}
+
+#[test]
+fn should_format_alternate_with_pattern() {
+    let c = Currency::new_float(1234.5, None);
+    let n = Currency::new_float(-1234.5, None);
+    let euro = Currency::new_float(
+        1234.5,
+        Some(
+            CurrencyOpts::new()
+                .set_symbol("€")
+                .set_separator(".")
+                .set_decimal(",")
+                .set_pattern("# !"),
+        ),
+    );
+
+    assert_eq!(format!("{c:#}"), c.format());
+    assert_eq!(format!("{n:#}"), "-$1,234.50");
+    assert_eq!(format!("{c:+#}"), "+$1,234.50");
+    assert_eq!(format!("{c:#.3}"), "$1,234.500");
+    assert_eq!(format!("{euro:#}"), "1.234,50 €");
+    assert_eq!(format!("{euro:>#12}|"), "  1.234,50 €|");
+    assert_eq!(format!("{n:<#12}|"), "-$1,234.50  |");
+    assert_eq!(format!("{n:#012}"), "  -$1,234.50");
+}
+
+#[test]
+fn should_apply_percentages() {
+    let pct = |v: f64| Percentage::new(v).unwrap();
+    let price = Currency::new_float(80., None);
+
+    assert_eq!(price.percent_of(pct(12.5)).unwrap().value(), 10.);
+    assert_eq!(price.add_percent(pct(12.5)).unwrap().value(), 90.);
+    assert_eq!(price.subtract_percent(pct(12.5)).unwrap().value(), 70.);
+    assert_eq!(price.markup(pct(25.)).unwrap().value(), 100.);
+    assert_eq!(price.margin(pct(20.)).unwrap().value(), 100.);
+    assert_eq!(
+        price
+            .percent_of(BasisPoints::new(125.).unwrap())
+            .unwrap()
+            .value(),
+        1.
+    );
+    assert_eq!(
+        price.margin(pct(100.)).unwrap_err(),
+        CurrencyErr::DivisionByZero
+    );
+
+    // a single rounding, 0.015 * 1.1 is 0.0165
+    let cents = Currency::new_float(0.015, Some(CurrencyOpts::new().set_precision(3)));
+    assert_eq!(cents.add_percent(pct(10.)).unwrap().value(), 0.017);
+
+    let even = CurrencyOpts::new().set_rounding_mode(RoundingMode::HalfEven);
+    let c = Currency::new_float(0.5, Some(even));
+    assert_eq!(c.percent_of(pct(5.)).unwrap().value(), 0.02);
+    assert_eq!(
+        Currency::new_float(0.5, None)
+            .percent_of(pct(5.))
+            .unwrap()
+            .value(),
+        0.03
+    );
+}
+
+#[test]
+fn should_apply_percentages_of_any_scale() {
+    let pct = |v: f64| Percentage::new(v).unwrap();
+    let price = |mode| Currency::new_float(80., Some(CurrencyOpts::new().set_rounding_mode(mode)));
+
+    let half = price(RoundingMode::HalfAwayFromZero);
+    let ceiling = price(RoundingMode::Ceiling);
+    let truncate = price(RoundingMode::Truncate);
+
+    for tiny in [pct(1e-40), pct(1e-300)] {
+        assert_eq!(half.percent_of(tiny).unwrap().value(), 0.);
+        assert_eq!(ceiling.percent_of(tiny).unwrap().value(), 0.01);
+        assert_eq!(half.add_percent(tiny).unwrap().value(), 80.);
+        assert_eq!(ceiling.add_percent(tiny).unwrap().value(), 80.01);
+        assert_eq!(half.subtract_percent(tiny).unwrap().value(), 80.);
+        assert_eq!(truncate.subtract_percent(tiny).unwrap().value(), 79.99);
+        assert_eq!(half.markup(tiny).unwrap().value(), 80.);
+        assert_eq!(half.margin(tiny).unwrap().value(), 80.);
+        assert_eq!(ceiling.margin(tiny).unwrap().value(), 80.01);
+    }
+
+    assert_eq!(truncate.margin(pct(-1e-40)).unwrap().value(), 79.99);
+
+    // 10% and 5% with 39 and 40 decimal places in their ratio
+    let ten = Percentage::from_decimal(Decimal::new(10_i128.pow(38), 37));
+    let five = Percentage::from_decimal(Decimal::new(5 * 10_i128.pow(37), 37));
+
+    assert_eq!(half.percent_of(ten).unwrap().value(), 8.);
+    assert_eq!(half.add_percent(ten).unwrap().value(), 88.);
+    assert_eq!(half.subtract_percent(ten).unwrap().value(), 72.);
+    assert_eq!(
+        Currency::new_float(90., None).margin(ten).unwrap().value(),
+        100.
+    );
+
+    let dime = |mode| Currency::new_float(0.1, Some(CurrencyOpts::new().set_rounding_mode(mode)));
+
+    assert_eq!(
+        dime(RoundingMode::HalfEven)
+            .percent_of(five)
+            .unwrap()
+            .value(),
+        0.
+    );
+    assert_eq!(
+        dime(RoundingMode::HalfAwayFromZero)
+            .percent_of(five)
+            .unwrap()
+            .value(),
+        0.01
+    );
+    assert_eq!(
+        dime(RoundingMode::HalfEven)
+            .subtract_percent(five)
+            .unwrap()
+            .value(),
+        0.1
+    );
+    assert_eq!(
+        dime(RoundingMode::HalfTowardZero)
+            .subtract_percent(five)
+            .unwrap()
+            .value(),
+        0.09
+    );
+}
+
+#[test]
+fn should_convert_and_compute_percentages() {
+    let bp: BasisPoints = Percentage::new(1.25).unwrap().into();
+    assert_eq!(bp, BasisPoints::new(125.).unwrap());
+    assert_eq!(Percentage::from(bp), Percentage::new(1.25).unwrap());
+    assert_eq!(bp.to_string(), "125 bp");
+    assert_eq!(Percentage::new(12.5).unwrap().to_string(), "12.5%");
+    assert_eq!(
+        "12.5%".parse::<Percentage>().unwrap(),
+        Percentage::new(12.5).unwrap()
+    );
+    assert_eq!("125 bp".parse::<BasisPoints>().unwrap(), bp);
+    assert!("abc%".parse::<Percentage>().is_err());
+    assert_eq!(
+        Percentage::new(f64::NAN).unwrap_err(),
+        CurrencyErr::NonFiniteNumber
+    );
+
+    let from = Currency::new_float(80., None);
+    let to = Currency::new_float(100., None);
+    assert_eq!(
+        Currency::percent_change(&from, &to).unwrap(),
+        Percentage::new(25.).unwrap()
+    );
+    assert_eq!(
+        Currency::percent_change(&to, &from).unwrap(),
+        Percentage::new(-20.).unwrap()
+    );
+    assert_eq!(
+        Currency::percent_change(
+            &Currency::new_float(3., None),
+            &Currency::new_float(4., None)
+        )
+        .unwrap()
+        .value()
+        .round(4, RoundingMode::HalfAwayFromZero)
+        .unwrap(),
+        Decimal::new(333333, 4)
+    );
+    assert_eq!(
+        Currency::percent_change(&Currency::new_float(0., None), &to).unwrap_err(),
+        CurrencyErr::DivisionByZero
+    );
+
+    let usd = Currency::new_float(1., Some(CurrencyOpts::new().set_code("USD")));
+    let eur = Currency::new_float(1., Some(CurrencyOpts::new().set_code("EUR")));
+    assert!(matches!(
+        Currency::percent_change(&usd, &eur),
+        Err(CurrencyErr::CurrencyMismatch { .. })
+    ));
+}
+
+#[test]
+fn should_add_and_extract_tax() {
+    let pct = |v: f64| Percentage::new(v).unwrap();
+    let vat = TaxCalculator::new().add_rate(TaxRate::new("VAT", pct(20.)));
+
+    let b = vat.add_tax(&Currency::new_float(10., None)).unwrap();
+    assert_eq!(b.tax().value(), 2.);
+    assert_eq!(b.gross().value(), 12.);
+
+    // 9.99 / 1.2 is 8.325, the tax takes the cent left over
+    let b = vat.extract_tax(&Currency::new_float(9.99, None)).unwrap();
+    assert_eq!(b.net().value(), 8.33);
+    assert_eq!(b.tax().value(), 1.66);
+    assert_eq!(b.taxes()[0].0, "VAT");
+
+    let compound = TaxCalculator::new()
+        .add_rate(TaxRate::new("GST", pct(5.)))
+        .add_rate(TaxRate::compound("QST", pct(9.975)));
+    let stacked = TaxCalculator::new()
+        .add_rate(TaxRate::new("GST", pct(5.)))
+        .add_rate(TaxRate::new("QST", pct(9.975)));
+
+    let hundred = Currency::new_float(100., None);
+    let b = compound.add_tax(&hundred).unwrap();
+    assert_eq!(b.taxes()[1].1.value(), 10.47);
+    assert_eq!(b.gross().value(), 115.47);
+    assert_eq!(stacked.add_tax(&hundred).unwrap().gross().value(), 114.98);
+
+    let b = compound
+        .extract_tax(&Currency::new_float(115.47, None))
+        .unwrap();
+    assert_eq!(b.net().value(), 100.);
+    assert_eq!(b.taxes()[0].1.value(), 5.);
+    assert_eq!(b.taxes()[1].1.value(), 10.47);
+
+    assert_eq!(
+        TaxCalculator::new()
+            .add_rate(TaxRate::new("X", pct(-100.)))
+            .extract_tax(&hundred)
+            .unwrap_err(),
+        CurrencyErr::DivisionByZero
+    );
+}
+
+#[test]
+fn should_reconcile_invoice_taxes() {
+    let vat = TaxCalculator::new().add_rate(TaxRate::new("VAT", Percentage::new(20.).unwrap()));
+    let lines = vec![Currency::new_float(0.13, None); 3];
+
+    let per_line = vat.invoice(&lines).unwrap();
+    assert_eq!(per_line.total().tax().value(), 0.09);
+
+    let per_invoice = vat
+        .clone()
+        .set_rounding(TaxRounding::PerInvoice)
+        .invoice(&lines)
+        .unwrap();
+    let taxes: Vec<f64> = per_invoice
+        .lines()
+        .iter()
+        .map(|l| l.tax().value())
+        .collect();
+    assert_eq!(taxes, vec![0.03, 0.02, 0.03]);
+    assert_eq!(per_invoice.total().tax().value(), 0.08);
+    assert_eq!(per_invoice.total().gross().value(), 0.47);
+
+    let inclusive = vat
+        .set_rounding(TaxRounding::PerInvoice)
+        .set_inclusive(true)
+        .invoice([
+            Currency::new_float(9.99, None),
+            Currency::new_float(4.99, None),
+            Currency::new_float(-2.5, None),
+        ])
+        .unwrap();
+    let total = inclusive.total();
+    assert_eq!(total.gross().value(), 12.48);
+    assert_eq!(total.net().value(), 10.4);
+    assert_eq!(
+        inclusive
+            .lines()
+            .iter()
+            .map(|l| l.net().minor_units())
+            .sum::<i128>(),
+        total.net().minor_units()
+    );
+    for line in inclusive.lines() {
+        assert_eq!(line.net() + line.tax(), *line.gross());
+    }
+
+    let usd = Currency::new_float(1., Some(CurrencyOpts::new().set_code("USD")));
+    let eur = Currency::new_float(1., Some(CurrencyOpts::new().set_code("EUR")));
+    assert!(matches!(
+        TaxCalculator::new().invoice([usd, eur]),
+        Err(CurrencyErr::CurrencyMismatch { .. })
+    ));
+    assert!(TaxCalculator::new()
+        .invoice(Vec::<Currency>::new())
+        .unwrap()
+        .total()
+        .gross()
+        .is_zero());
+}
+
+#[test]
+fn should_compute_progressive_tax() {
+    let usd = |v: f64| Currency::new_float(v, CurrencyOpts::for_code("USD"));
+    let pct = |v: f64| Percentage::new(v).unwrap();
+
+    let schedule = TaxSchedule::new()
+        .add_bracket(usd(10000.), pct(10.))
+        .and_then(|s| s.add_bracket(usd(40000.), pct(20.)))
+        .and_then(|s| s.add_bracket(usd(100000.), pct(40.)))
+        .unwrap();
+
+    let tax = schedule.tax(&usd(50000.)).unwrap();
+    assert_eq!(tax.tax().value(), 5000.);
+    assert_eq!(tax.tax().code(), Some("USD"));
+
+    let taxable: Vec<f64> = tax.brackets().iter().map(|b| b.taxable().value()).collect();
+    assert_eq!(taxable, vec![30000., 10000., 0.]);
+
+    let taxes: Vec<f64> = tax.brackets().iter().map(|b| b.tax().value()).collect();
+    assert_eq!(taxes, vec![3000., 2000., 0.]);
+
+    assert!(schedule.tax(&usd(5000.)).unwrap().tax().is_zero());
+    assert!(schedule.tax(&usd(-5000.)).unwrap().tax().is_zero());
+
+    // each bracket owes half a cent, the total is rounded once
+    let cents = TaxSchedule::new()
+        .add_bracket(Currency::new_float(0., None), pct(5.))
+        .and_then(|s| s.add_bracket(Currency::new_float(0.1, None), pct(5.)))
+        .unwrap()
+        .tax(&Currency::new_float(0.2, None))
+        .unwrap();
+    assert_eq!(cents.tax().value(), 0.01);
+    assert_eq!(
+        cents
+            .brackets()
+            .iter()
+            .map(|b| b.tax().minor_units())
+            .sum::<i128>(),
+        1
+    );
+}
+
+#[test]
+fn should_reject_invalid_tax_schedules() {
+    let usd = |v: f64| Currency::new_float(v, CurrencyOpts::for_code("USD"));
+    let eur = Currency::new_float(100., CurrencyOpts::for_code("EUR"));
+    let pct = Percentage::new(10.).unwrap();
+
+    let err = TaxSchedule::new()
+        .add_bracket(usd(100.), pct)
+        .and_then(|s| s.add_bracket(usd(100.), pct))
+        .unwrap_err();
+    assert!(matches!(err, CurrencyErr::InvalidSchedule(_)));
+    assert_eq!(
+        err.to_string(),
+        "invalid tax schedule: threshold 100.00 is not above 100.00"
+    );
+
+    assert!(matches!(
+        TaxSchedule::new()
+            .add_bracket(usd(100.), pct)
+            .and_then(|s| s.add_bracket(eur.clone(), pct)),
+        Err(CurrencyErr::CurrencyMismatch { .. })
+    ));
+
+    let schedule = TaxSchedule::new().add_bracket(usd(0.), pct).unwrap();
+    assert!(matches!(
+        schedule.tax(&eur),
+        Err(CurrencyErr::CurrencyMismatch { .. })
+    ));
+}
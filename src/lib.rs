@@ -35,15 +35,45 @@
 //! ```
 
 mod currency;
+mod currency_backend;
+mod currency_bag;
+mod currency_decimal;
 mod currency_err;
+mod currency_exchange;
+mod currency_grouping;
 mod currency_impl;
+mod currency_iso;
+mod currency_locale;
+mod currency_math;
 mod currency_opts;
+mod currency_parser;
+mod currency_percent;
+mod currency_rate_history;
+#[cfg(feature = "fancy-regex")]
 mod currency_regex;
+mod currency_rounding;
+#[cfg(feature = "serde")]
+pub mod currency_ser;
+mod currency_tax;
 
 pub use currency::Currency;
-pub use currency_err::CurrencyErr;
+pub use currency_backend::Backend;
+pub use currency_bag::MoneyBag;
+pub use currency_decimal::Decimal;
+pub use currency_err::{CurrencyErr, IoError, ParseErrKind, ParseError};
+pub use currency_exchange::{ExchangeRate, RateProvider, RateTable};
+pub use currency_grouping::Grouping;
+pub use currency_iso::IsoCurrency;
+pub use currency_locale::Locale;
+pub(crate) use currency_math::DecimalErr;
 pub use currency_opts::CurrencyOpts;
-pub(crate) use currency_regex::*;
+pub use currency_percent::{BasisPoints, Percentage};
+pub use currency_rate_history::{DatedRateTable, RateDate};
+pub use currency_rounding::RoundingMode;
+pub use currency_tax::{
+    BracketTax, ScheduleTax, TaxBracket, TaxBreakdown, TaxCalculator, TaxInvoice, TaxRate,
+    TaxRounding, TaxSchedule,
+};
 
 #[cfg(test)]
 mod currency_test;